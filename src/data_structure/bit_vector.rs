@@ -0,0 +1,213 @@
+use crate::utils::bitset::bitset;
+
+pub mod bit_vector {
+    use super::bitset::BitSet;
+    use std::cmp::*;
+
+    /// Number of words per superblock
+    const BLOCKS: usize = 8;
+    const SUPER: usize = 64 * BLOCKS;
+    /// Every `SAMPLE`-th occurrence is sampled to narrow down `select`
+    const SAMPLE: usize = 512;
+
+    /// Static succinct bit vector
+    ///
+    /// `rank` is O(1) and `select` is O(log(SAMPLE)) in practice.
+    #[derive(Debug, Clone)]
+    pub struct BitVector {
+        words: Vec<u64>,
+        size: usize,
+        large: Vec<usize>,
+        small: Vec<u16>,
+        samples1: Vec<usize>,
+        samples0: Vec<usize>,
+    }
+
+    impl BitVector {
+        pub fn new(bits: &BitSet) -> Self {
+            let words = bits.words().to_vec();
+            let size = bits.len();
+            let mut large = vec![0; words.len() / BLOCKS + 1];
+            let mut small = vec![0; words.len() + 1];
+            let mut acc = 0;
+            for i in 0..=words.len() {
+                if i % BLOCKS == 0 {
+                    large[i / BLOCKS] = acc;
+                }
+                small[i] = (acc - large[i / BLOCKS]) as u16;
+                if i < words.len() {
+                    acc += words[i].count_ones() as usize;
+                }
+            }
+            let mut ret = Self { words, size, large, small, samples1: vec![], samples0: vec![] };
+            ret.samples1 = ret.build_samples(true);
+            ret.samples0 = ret.build_samples(false);
+            ret
+        }
+
+        fn build_samples(&self, b: bool) -> Vec<usize> {
+            let total = self.count(b);
+            let n = self.large.len();
+            let mut samples = vec![];
+            let (mut s, mut k) = (0, 0);
+            while k < total {
+                while s + 1 < n && self.rank_super(s + 1, b) <= k {
+                    s += 1;
+                }
+                samples.push(s);
+                k += SAMPLE;
+            }
+            samples
+        }
+
+        fn rank_super(&self, s: usize, b: bool) -> usize {
+            if b {
+                self.large[s]
+            } else {
+                min(s * SUPER, self.size) - self.large[s]
+            }
+        }
+
+        fn rank_block(&self, i: usize, b: bool) -> usize {
+            let c = self.small[i] as usize;
+            if b { c } else { (i % BLOCKS) * 64 - c }
+        }
+
+        pub fn len(&self) -> usize {
+            self.size
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.size == 0
+        }
+
+        pub fn access(&self, i: usize) -> bool {
+            assert!(i < self.size);
+            self.words[i >> 6] >> (i & 63) & 1 == 1
+        }
+
+        /// Number of `b` in [0..i)
+        pub fn rank(&self, i: usize, b: bool) -> usize {
+            assert!(i <= self.size);
+            let q = i >> 6;
+            let mut ret = self.large[q / BLOCKS] + self.small[q] as usize;
+            let r = i & 63;
+            if r != 0 {
+                ret += (self.words[q] & ((1 << r) - 1)).count_ones() as usize;
+            }
+            if b { ret } else { i - ret }
+        }
+
+        pub fn rank1(&self, i: usize) -> usize {
+            self.rank(i, true)
+        }
+
+        pub fn rank0(&self, i: usize) -> usize {
+            self.rank(i, false)
+        }
+
+        /// Total number of `b`
+        pub fn count(&self, b: bool) -> usize {
+            self.rank(self.size, b)
+        }
+
+        /// Position of the `k`-th (0-indexed) `b`
+        pub fn select(&self, k: usize, b: bool) -> Option<usize> {
+            if k >= self.count(b) { return None }
+            let samples = if b { &self.samples1 } else { &self.samples0 };
+            let mut lo = samples[k / SAMPLE];
+            let mut hi = match samples.get(k / SAMPLE + 1) {
+                Some(&s) => s + 1,
+                None => self.large.len(),
+            };
+            while hi - lo > 1 {
+                let mid = (lo + hi) / 2;
+                if self.rank_super(mid, b) <= k {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            let k = k - self.rank_super(lo, b);
+            let mut q = lo * BLOCKS;
+            let end = min(q + BLOCKS, self.words.len());
+            while q + 1 < end && self.rank_block(q + 1, b) <= k {
+                q += 1;
+            }
+            let mut w = if b { self.words[q] } else { !self.words[q] };
+            for _ in 0..k - self.rank_block(q, b) {
+                w &= w - 1;
+            }
+            Some((q << 6) | w.trailing_zeros() as usize)
+        }
+
+        pub fn select1(&self, k: usize) -> Option<usize> {
+            self.select(k, true)
+        }
+
+        pub fn select0(&self, k: usize) -> Option<usize> {
+            self.select(k, false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bit_vector::*;
+    use super::bitset::BitSet;
+
+    #[test]
+    fn test_bit_vector_small() {
+        let mut set = BitSet::new(10);
+        for &i in &[1, 2, 5, 9] {
+            set.set(i, true);
+        }
+        let bv = BitVector::new(&set);
+        assert_eq!(bv.rank1(0), 0);
+        assert_eq!(bv.rank1(3), 2);
+        assert_eq!(bv.rank1(10), 4);
+        assert_eq!(bv.rank0(10), 6);
+        assert_eq!(bv.select1(0), Some(1));
+        assert_eq!(bv.select1(3), Some(9));
+        assert_eq!(bv.select1(4), None);
+        assert_eq!(bv.select0(2), Some(4));
+        assert_eq!(bv.select0(5), Some(8));
+        assert_eq!(bv.select0(6), None);
+    }
+
+    #[test]
+    fn test_bit_vector_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for &(size, p) in &[(0, 0.5), (1, 0.5), (64, 0.5), (5000, 0.5), (20000, 0.01), (20000, 0.99)] {
+            let mut set = BitSet::new(size);
+            let v = (0..size).map(|_| rng.gen_bool(p)).collect::<Vec<_>>();
+            for (i, &b) in v.iter().enumerate() {
+                set.set(i, b);
+            }
+            let bv = BitVector::new(&set);
+            let mut ones = 0;
+            let (mut pos1, mut pos0) = (vec![], vec![]);
+            for (i, &b) in v.iter().enumerate() {
+                assert_eq!(bv.rank1(i), ones);
+                assert_eq!(bv.rank0(i), i - ones);
+                assert_eq!(bv.access(i), b);
+                if b {
+                    ones += 1;
+                    pos1.push(i);
+                } else {
+                    pos0.push(i);
+                }
+            }
+            assert_eq!(bv.rank1(size), ones);
+            for (k, &i) in pos1.iter().enumerate() {
+                assert_eq!(bv.select1(k), Some(i));
+            }
+            for (k, &i) in pos0.iter().enumerate() {
+                assert_eq!(bv.select0(k), Some(i));
+            }
+            assert_eq!(bv.select1(pos1.len()), None);
+            assert_eq!(bv.select0(pos0.len()), None);
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use crate::utils::hasher::hasher;
+
+pub mod hash_map {
+    use super::hasher::splitmix64;
+    use std::iter::FromIterator;
+
+    /// Open addressing (linear probing) hash map for `u64` keys
+    ///
+    /// The hash is seeded at random, so it is safe against anti-hash tests.
+    #[derive(Debug, Clone)]
+    pub struct U64HashMap<V> {
+        slots: Vec<Option<(u64, V)>>,
+        len: usize,
+        shift: u32,
+        seed: u64,
+    }
+
+    impl<V> U64HashMap<V> {
+        pub fn new() -> Self {
+            Self::with_capacity(0)
+        }
+
+        pub fn with_capacity(cap: usize) -> Self {
+            let cap = std::cmp::max(8, (cap * 2).next_power_of_two());
+            let slots = (0..cap).map(|_| None).collect();
+            let shift = 64 - cap.trailing_zeros();
+            Self { slots, len: 0, shift, seed: rand::random() }
+        }
+
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Number of slots
+        pub fn capacity(&self) -> usize {
+            self.slots.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        fn hash(&self, key: u64) -> usize {
+            (splitmix64(key ^ self.seed) >> self.shift) as usize
+        }
+
+        /// Index of the slot holding `key`, or of the empty slot where it would go
+        fn position(&self, key: u64) -> usize {
+            let mask = self.slots.len() - 1;
+            let mut i = self.hash(key);
+            loop {
+                match &self.slots[i] {
+                    Some((k, _)) if *k != key => i = (i + 1) & mask,
+                    _ => return i,
+                }
+            }
+        }
+
+        /// Grows the table if one more key would make it more than half full.
+        fn reserve_one(&mut self) {
+            if (self.len + 1) * 2 <= self.slots.len() { return }
+            let cap = self.slots.len() * 2;
+            let old = std::mem::replace(&mut self.slots, (0..cap).map(|_| None).collect());
+            self.shift -= 1;
+            for (k, v) in old.into_iter().flatten() {
+                let i = self.position(k);
+                self.slots[i] = Some((k, v));
+            }
+        }
+
+        /// Returns the old value if `key` was already present.
+        pub fn insert(&mut self, key: u64, value: V) -> Option<V> {
+            let idx = self.position(key);
+            self.entry_at(key, idx).replace(value)
+        }
+
+        fn entry_at(&mut self, key: u64, idx: usize) -> Entry<'_, V> {
+            Entry { map: self, key, idx }
+        }
+
+        pub fn get(&self, key: u64) -> Option<&V> {
+            self.slots[self.position(key)].as_ref().map(|(_, v)| v)
+        }
+
+        pub fn get_mut(&mut self, key: u64) -> Option<&mut V> {
+            let i = self.position(key);
+            self.slots[i].as_mut().map(|(_, v)| v)
+        }
+
+        pub fn contains_key(&self, key: u64) -> bool {
+            self.get(key).is_some()
+        }
+
+        /// The table grows only when the entry inserts a new key.
+        pub fn entry(&mut self, key: u64) -> Entry<'_, V> {
+            let idx = self.position(key);
+            self.entry_at(key, idx)
+        }
+
+        pub fn iter(&self) -> U64HashMapIter<'_, V> {
+            U64HashMapIter { slots: self.slots.iter() }
+        }
+    }
+
+    impl<V> Default for U64HashMap<V> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<V> FromIterator<(u64, V)> for U64HashMap<V> {
+        fn from_iter<I: IntoIterator<Item = (u64, V)>>(iter: I) -> Self {
+            let mut map = Self::new();
+            for (k, v) in iter {
+                map.insert(k, v);
+            }
+            map
+        }
+    }
+
+    pub struct Entry<'a, V> {
+        map: &'a mut U64HashMap<V>,
+        key: u64,
+        idx: usize,
+    }
+
+    impl<'a, V> Entry<'a, V> {
+        /// Puts the key in its empty slot, growing the table first if needed.
+        fn vacant_insert(&mut self, value: V) {
+            if (self.map.len + 1) * 2 > self.map.slots.len() {
+                self.map.reserve_one();
+                self.idx = self.map.position(self.key);
+            }
+            self.map.slots[self.idx] = Some((self.key, value));
+            self.map.len += 1;
+        }
+
+        fn replace(mut self, value: V) -> Option<V> {
+            match &mut self.map.slots[self.idx] {
+                Some((_, v)) => Some(std::mem::replace(v, value)),
+                None => {
+                    self.vacant_insert(value);
+                    None
+                }
+            }
+        }
+
+        pub fn or_insert_with<F: FnOnce() -> V>(mut self, f: F) -> &'a mut V {
+            if self.map.slots[self.idx].is_none() {
+                self.vacant_insert(f());
+            }
+            &mut self.map.slots[self.idx].as_mut().unwrap().1
+        }
+
+        pub fn or_insert(self, default: V) -> &'a mut V {
+            self.or_insert_with(|| default)
+        }
+    }
+
+    impl<'a, V: Default> Entry<'a, V> {
+        pub fn or_default(self) -> &'a mut V {
+            self.or_insert_with(V::default)
+        }
+    }
+
+    pub struct U64HashMapIter<'a, V> {
+        slots: std::slice::Iter<'a, Option<(u64, V)>>,
+    }
+
+    impl<'a, V> Iterator for U64HashMapIter<'a, V> {
+        type Item = (u64, &'a V);
+        fn next(&mut self) -> Option<Self::Item> {
+            self.slots.by_ref().flatten().next().map(|(k, v)| (*k, v))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hash_map::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_u64_hash_map() {
+        let mut map = U64HashMap::new();
+        assert!(map.is_empty());
+        assert_eq!(map.insert(3, "a"), None);
+        assert_eq!(map.insert(5, "b"), None);
+        assert_eq!(map.insert(3, "c"), Some("a"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(3), Some(&"c"));
+        assert_eq!(map.get(4), None);
+        *map.get_mut(5).unwrap() = "d";
+        assert_eq!(map.get(5), Some(&"d"));
+        assert_eq!(*map.entry(7).or_insert("e"), "e");
+        assert_eq!(*map.entry(7).or_insert("f"), "e");
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn test_u64_hash_map_grows_only_on_insert() {
+        let mut map = U64HashMap::with_capacity(4);
+        for k in 0..4 {
+            map.insert(k, k);
+        }
+        let cap = map.capacity();
+        for k in 0..4 {
+            *map.entry(k).or_default() += 1;
+            map.insert(k, k + 1);
+        }
+        assert_eq!(map.capacity(), cap);
+        map.insert(4, 4);
+        assert!(map.capacity() > cap);
+        assert_eq!((0..5).map(|k| map.get(k).copied()).collect::<Vec<_>>(), (0..5).map(|k| Some(k + (k < 4) as u64)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_u64_hash_map_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        let mut map = U64HashMap::<u64>::default();
+        let mut naive = HashMap::new();
+        for _ in 0..100000 {
+            let k = if rng.gen_bool(0.5) { rng.gen_range(0..1000) } else { rng.gen::<u64>() };
+            let v = rng.gen_range(0..10);
+            *map.entry(k).or_default() += v;
+            *naive.entry(k).or_insert(0) += v;
+        }
+        assert_eq!(map.len(), naive.len());
+        for (k, v) in map.iter() {
+            assert_eq!(naive[&k], *v);
+        }
+        for (k, v) in naive.iter() {
+            assert_eq!(map.get(*k), Some(v));
+        }
+    }
+}
@@ -7,3 +7,5 @@ pub mod lazy_segtree;
 pub mod trie;
 pub mod fenwick_tree;
 pub mod multiset;
+pub mod bit_vector;
+pub mod hash_map;
@@ -1,6 +1,9 @@
+use crate::math::algebra::num_trait;
+
 pub mod skew_heap {
+    use super::num_trait::*;
     use std::ops::*;
-    use std::mem::swap;
+    use std::mem::{swap, take};
 
     #[derive(Debug)]
     pub struct SkewHeap<T: Ord>(Option<Box<SkewNode<T>>>);
@@ -64,6 +67,88 @@ pub mod skew_heap {
             })
         }
     }
+
+    /// Meldable heap of `(key, value)` pairs that can add a constant to all keys in O(1)
+    #[derive(Debug)]
+    pub struct LazySkewHeap<K, V>(Option<Box<LazySkewNode<K, V>>>);
+
+    #[derive(Debug)]
+    struct LazySkewNode<K, V> {
+        key: K,
+        value: V,
+        lazy: K,
+        left: LazySkewHeap<K, V>,
+        right: LazySkewHeap<K, V>,
+    }
+
+    impl<K: Monoid + Ord, V> LazySkewHeap<K, V> {
+        pub fn new() -> Self {
+            Self(None)
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.0.is_none()
+        }
+
+        /// Adds `x` to every key
+        pub fn add_all(&mut self, x: K) {
+            if let Some(node) = self.0.as_mut() {
+                node.key = node.key + x;
+                node.lazy = node.lazy + x;
+            }
+        }
+
+        fn push_down(node: &mut LazySkewNode<K, V>) {
+            let lazy = node.lazy;
+            node.left.add_all(lazy);
+            node.right.add_all(lazy);
+            node.lazy = K::zero();
+        }
+
+        pub fn push(&mut self, key: K, value: V) {
+            let node = LazySkewNode { key, value, lazy: K::zero(), left: Self::new(), right: Self::new() };
+            *self = take(self) + Self(Some(Box::new(node)));
+        }
+
+        /// Removes the pair with the minimum key
+        pub fn pop(&mut self) -> Option<(K, V)> {
+            self.0.take().map(|mut node| {
+                Self::push_down(&mut node);
+                let LazySkewNode { key, value, left, right, .. } = *node;
+                *self = left + right;
+                (key, value)
+            })
+        }
+
+        pub fn peek(&self) -> Option<(K, &V)> {
+            self.0.as_ref().map(|node| (node.key, &node.value))
+        }
+    }
+
+    impl<K: Monoid + Ord, V> Default for LazySkewHeap<K, V> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<K: Monoid + Ord, V> Add for LazySkewHeap<K, V> {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self {
+            Self(match (self.0, rhs.0) {
+                (None, r) => r,
+                (l, None) => l,
+                (Some(mut l), Some(mut r)) => {
+                    if l.key > r.key {
+                        swap(&mut l, &mut r);
+                    }
+                    Self::push_down(&mut l);
+                    l.right = take(&mut l.right) + Self(Some(r));
+                    swap(&mut l.left, &mut l.right);
+                    Some(l)
+                }
+            })
+        }
+    }
 }
 
 #[cfg(test)]
@@ -101,4 +186,27 @@ mod tests {
         heap.pop();
         assert_eq!(heap.peek(), Some(&5));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_lazy_skew_heap() {
+        let mut heap = LazySkewHeap::new();
+        heap.push(5, 'a');
+        heap.push(3, 'b');
+        heap.add_all(10);
+        heap.push(14, 'c');
+        let mut other = LazySkewHeap::new();
+        other.push(1, 'd');
+        other.push(20, 'e');
+        other.add_all(-5);
+        let mut heap = heap + other;
+        assert_eq!(heap.peek(), Some((-4, &'d')));
+        assert_eq!(heap.pop(), Some((-4, 'd')));
+        heap.add_all(1);
+        assert_eq!(heap.pop(), Some((14, 'b')));
+        assert_eq!(heap.pop(), Some((15, 'c')));
+        assert_eq!(heap.pop(), Some((16, 'a')));
+        assert_eq!(heap.pop(), Some((16, 'e')));
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+    }
+}
@@ -5,38 +5,121 @@ pub mod graph {
     use std::cmp::*;
     use std::ops::*;
 
+    /// `cost` is an arbitrary payload; algorithms require numeric traits on it
+    /// only where they actually use it as a weight.
+    ///
+    /// Both directions of an undirected edge share the same `id`.
+    /// Edges compare by `to` and `cost` first, as they did before `from` and `id` were added.
     #[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone)]
     pub struct Edge<T> {
         pub to: usize,
         pub cost: T,
+        pub from: usize,
+        pub id: usize,
     }
-    impl<T: AbelGroup + Eq + Ord> Edge<T> {
-        pub fn new(to: usize, cost: T) -> Self {
-            Self { to, cost }
+    impl<T> Edge<T> {
+        pub fn with_id(from: usize, to: usize, cost: T, id: usize) -> Self {
+            Self { to, cost, from, id }
         }
     }
 
+    /// Adjacency list and the next edge id
+    ///
+    /// Build it with `Graph::new` and the `add_edge*` methods, or from a hand-made
+    /// adjacency list with `Graph::from_adjacency`; `Graph(adjacency)` no longer compiles.
+    /// Edges pushed through `IndexMut` carry their own ids, which `edge_count` does not see.
     #[derive(Debug, Clone)]
-    pub struct Graph<T>(pub Vec<Vec<Edge<T>>>);
+    pub struct Graph<T>(pub Vec<Vec<Edge<T>>>, usize);
 
-    impl<T: AbelGroup + Eq + Ord> Graph<T> {
+    /// Common interface of graph representations whose adjacency
+    /// of each vertex can be seen as a slice of `Edge`s.
+    pub trait AdjacencyList {
+        type Cost;
+        fn vertex_count(&self) -> usize;
+        fn edges(&self, v: usize) -> &[Edge<Self::Cost>];
+    }
+
+    impl<T> AdjacencyList for Graph<T> {
+        type Cost = T;
+        fn vertex_count(&self) -> usize {
+            self.0.len()
+        }
+        fn edges(&self, v: usize) -> &[Edge<T>] {
+            &self.0[v]
+        }
+    }
+
+    impl<T> Graph<T> {
         pub fn new(n: usize) -> Self {
-            let graph = vec![vec![]; n];
-            Self(graph)
+            let graph = (0..n).map(|_| vec![]).collect();
+            Self(graph, 0)
+        }
+
+        /// Number of added edges (an undirected edge counts once)
+        pub fn edge_count(&self) -> usize {
+            self.1
+        }
+
+        /// Returns the id of the new edge.
+        pub fn add_edge(&mut self, from: usize, to: usize, cost: T) -> usize {
+            let id = self.1;
+            self.1 += 1;
+            self[from].push(Edge::with_id(from, to, cost, id));
+            id
+        }
+
+        /// All edges sorted by id; undirected edges appear once.
+        pub fn edge_list(&self) -> Vec<&Edge<T>> {
+            let m = self.0.iter().flatten().map(|e| e.id + 1).fold(self.1, max);
+            let mut ret: Vec<Option<&Edge<T>>> = vec![None; m];
+            for edge in self.0.iter().flatten() {
+                ret[edge.id].get_or_insert(edge);
+            }
+            ret.into_iter().flatten().collect()
+        }
+    }
+
+    impl<T: Clone> Graph<T> {
+        /// Returns the id shared by both directions.
+        pub fn add_edge_undirected(&mut self, from: usize, to: usize, cost: T) -> usize {
+            let id = self.1;
+            self.1 += 1;
+            self[from].push(Edge::with_id(from, to, cost.clone(), id));
+            self[to].push(Edge::with_id(to, from, cost, id));
+            id
         }
+    }
 
-        pub fn add_edge(&mut self, from: usize, to: usize, cost: T) {
-            self[from].push(Edge::new(to, cost));
+    impl<T: Zero + Clone> Graph<T> {
+        pub fn add_unweighted_edge(&mut self, from: usize, to: usize) -> usize {
+            self.add_edge_undirected(from, to, T::zero())
         }
+    }
 
-        pub fn add_edge_undirected(&mut self, from: usize, to: usize, cost: T) {
-            self[from].push(Edge::new(to, cost));
-            self[to].push(Edge::new(from, cost));
+    impl<T> Graph<T> {
+        /// `adjacency[v]` lists the `(to, cost)` pairs leaving `v`;
+        /// edges are numbered in the order they appear.
+        pub fn from_adjacency(adjacency: Vec<Vec<(usize, T)>>) -> Self {
+            let mut graph = Self::new(adjacency.len());
+            for (v, edges) in adjacency.into_iter().enumerate() {
+                for (to, cost) in edges {
+                    graph.add_edge(v, to, cost);
+                }
+            }
+            graph
         }
+    }
 
-        pub fn add_unweighted_edge(&mut self, from: usize, to: usize) {
-            self[from].push(Edge::new(to, T::zero()));
-            self[to].push(Edge::new(from, T::zero()));
+    impl<T> From<Vec<Vec<Edge<T>>>> for Graph<T> {
+        /// Keeps the given ids and sets `from` to the owning vertex.
+        fn from(mut adjacency: Vec<Vec<Edge<T>>>) -> Self {
+            let next = adjacency.iter().flatten().map(|e| e.id + 1).max().unwrap_or(0);
+            for (v, edges) in adjacency.iter_mut().enumerate() {
+                for edge in edges.iter_mut() {
+                    edge.from = v;
+                }
+            }
+            Self(adjacency, next)
         }
     }
 
@@ -53,3 +136,54 @@ pub mod graph {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::graph::*;
+
+    #[test]
+    fn test_graph_edge_ids() {
+        let mut g = Graph::new(3);
+        assert_eq!(g.add_edge(0, 1, 5), 0);
+        assert_eq!(g.add_edge_undirected(1, 2, 7), 1);
+        assert_eq!(g.add_edge(2, 0, 1), 2);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g[1], vec![Edge::with_id(1, 2, 7, 1)]);
+        assert_eq!(g[2], vec![Edge::with_id(2, 1, 7, 1), Edge::with_id(2, 0, 1, 2)]);
+        let list = g.edge_list();
+        assert_eq!(list.iter().map(|e| (e.from, e.to, e.id)).collect::<Vec<_>>(), vec![(0, 1, 0), (1, 2, 1), (2, 0, 2)]);
+    }
+
+    #[test]
+    fn test_graph_from_adjacency() {
+        let mut g = Graph::from_adjacency(vec![vec![(1, 3)], vec![(0, 3), (2, 4)], vec![]]);
+        assert_eq!(g[1], vec![Edge::with_id(1, 0, 3, 1), Edge::with_id(1, 2, 4, 2)]);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g.add_edge(2, 0, 5), 3);
+        let h = Graph::from(vec![vec![Edge::with_id(0, 1, 1, 4)], vec![Edge::with_id(0, 0, 1, 2)]]);
+        assert_eq!((h[1][0].from, h.edge_count()), (1, 5));
+        assert!(Edge::with_id(9, 1, 2, 9) < Edge::with_id(0, 2, 1, 0));
+    }
+
+    #[test]
+    fn test_graph_pushed_edges() {
+        let mut g = Graph::new(3);
+        g.add_edge(0, 1, 1);
+        g[1].push(Edge::with_id(1, 2, 2, 5));
+        let list = g.edge_list();
+        assert_eq!(list.iter().map(|e| (e.from, e.id)).collect::<Vec<_>>(), vec![(0, 0), (1, 5)]);
+    }
+
+    #[test]
+    fn test_graph_payload() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Road { name: &'static str, toll: bool }
+        let mut g = Graph::new(2);
+        g.add_edge_undirected(0, 1, Road { name: "a", toll: true });
+        assert_eq!(g[1][0].cost.name, "a");
+        assert!(g[0][0].cost.toll);
+        let mut h = Graph::<()>::new(2);
+        h.add_edge(1, 0, ());
+        assert_eq!(h[1][0].to, 0);
+    }
+}
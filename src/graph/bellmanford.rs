@@ -6,56 +6,144 @@ pub mod bellmanford {
     use super::num_trait::*;
     use super::graph::*;
     use std::cmp::*;
+    use std::collections::VecDeque;
 
     #[derive(Debug, Clone)]
     pub struct BellmanFord<'a, T> {
         graph: &'a Graph<T>,
         pub dists: Vec<T>,
-        backs: Vec<isize>,
+        /// `neg_inf[v]` is true iff `v` is reachable from a negative cycle
+        /// reachable from the source; `dists[v]` is meaningless then.
+        pub neg_inf: Vec<bool>,
+        /// previous vertex and the edge taken from it
+        parents: Vec<Option<(usize, &'a Edge<T>)>>,
+        cycle: Option<(Vec<usize>, Vec<usize>)>,
     }
 
-    impl<'a, T: AbelGroup + Bounded + Eq + Ord> BellmanFord<'a, T> {
+    impl<'a, T: AbelGroup + Bounded + Ord> BellmanFord<'a, T> {
         pub fn new(graph: &'a Graph<T>) -> Self {
             let n = graph.0.len();
             let dists = vec![T::max_value(); n];
-            let backs = vec![-1; n];
-            Self { graph, dists, backs }
+            let neg_inf = vec![false; n];
+            let parents = vec![None; n];
+            Self { graph, dists, neg_inf, parents, cycle: None }
         }
 
+        fn reset(&mut self) {
+            *self = Self::new(self.graph);
+        }
+
+        /// Returns `false` if a negative cycle is reachable from `s`.
         pub fn build_graph(&mut self, s: usize) -> bool {
+            let graph = self.graph;
             self.dists[s] = T::zero();
             let n = self.dists.len();
             for i in 0..n {
-                let mut updated = false;
-                for from in 0..n {
-                    let edges = &self.graph[from];
-                    for edge in edges.iter() {
-                        if self.dists[from] != T::max_value() &&
-                           self.dists[edge.to] > self.dists[from] + edge.cost {
-                            self.dists[edge.to] = self.dists[from] + edge.cost;
-                            self.backs[edge.to] = from as isize;
-                            updated = true;
+                let mut updated = vec![];
+                for (v, edges) in graph.0.iter().enumerate() {
+                    if self.dists[v] == T::max_value() { continue }
+                    for edge in edges {
+                        if self.dists[edge.to] > self.dists[v] + edge.cost {
+                            self.dists[edge.to] = self.dists[v] + edge.cost;
+                            self.parents[edge.to] = Some((v, edge));
+                            updated.push(edge.to);
                         }
                     }
                 }
-                if !updated {
+                if updated.is_empty() {
                     break
                 }
                 if i == n - 1 {
+                    self.cycle = Some(self.find_cycle(updated[0]));
+                    self.mark_negative_infinity(updated);
                     return false
                 }
             }
             true
         }
 
+        /// Queue based variant (SPFA), usually much faster on sparse graphs.
+        ///
+        /// When a negative cycle is detected, falls back to `build_graph`
+        /// so that `neg_inf` and `negative_cycle` are filled in the same way.
+        pub fn build_graph_spfa(&mut self, s: usize) -> bool {
+            let graph = self.graph;
+            let n = self.dists.len();
+            let mut len = vec![0; n];
+            let mut in_queue = vec![false; n];
+            let mut q = VecDeque::new();
+            self.dists[s] = T::zero();
+            q.push_back(s);
+            in_queue[s] = true;
+            while let Some(v) = q.pop_front() {
+                in_queue[v] = false;
+                for edge in graph[v].iter() {
+                    if self.dists[edge.to] > self.dists[v] + edge.cost {
+                        self.dists[edge.to] = self.dists[v] + edge.cost;
+                        self.parents[edge.to] = Some((v, edge));
+                        len[edge.to] = len[v] + 1;
+                        if len[edge.to] >= n {
+                            self.reset();
+                            return self.build_graph(s);
+                        }
+                        if !in_queue[edge.to] {
+                            in_queue[edge.to] = true;
+                            q.push_back(edge.to);
+                        }
+                    }
+                }
+            }
+            true
+        }
+
+        fn find_cycle(&self, mut v: usize) -> (Vec<usize>, Vec<usize>) {
+            for _ in 0..self.dists.len() {
+                v = self.parents[v].unwrap().0;
+            }
+            let (mut vertices, mut edges) = (vec![], vec![]);
+            let start = v;
+            loop {
+                let (u, edge) = self.parents[v].unwrap();
+                vertices.push(v);
+                edges.push(edge.id);
+                v = u;
+                if v == start { break }
+            }
+            vertices.reverse();
+            edges.reverse();
+            edges.rotate_left(1);
+            (vertices, edges)
+        }
+
+        fn mark_negative_infinity(&mut self, mut stack: Vec<usize>) {
+            for &v in stack.iter() {
+                self.neg_inf[v] = true;
+            }
+            while let Some(v) = stack.pop() {
+                for edge in self.graph[v].iter() {
+                    if !self.neg_inf[edge.to] {
+                        self.neg_inf[edge.to] = true;
+                        stack.push(edge.to);
+                    }
+                }
+            }
+        }
+
+        /// A negative cycle reachable from the source, as `(vertices, edge ids)`
+        /// where the `i`-th edge goes from `vertices[i]` to `vertices[(i + 1) % len]`.
+        pub fn negative_cycle(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+            self.cycle.clone()
+        }
+
         pub fn restore(&self, mut to: isize) -> Vec<isize> {
+            let back = |v: isize| self.parents[v as usize].map_or(-1, |(u, _)| u as isize);
             let mut path = vec![];
-            if self.backs[to as usize] < 0 {
+            if back(to) < 0 {
                 path
             } else {
                 while to > 0 {
                     path.push(to);
-                    to = self.backs[to as usize];
+                    to = back(to);
                 }
                 path.reverse();
                 path
@@ -131,4 +219,61 @@ mod tests {
         let mut g = BellmanFord::new(&g);
         assert!(!g.build_graph(0));
     }
+
+    #[test]
+    fn test_bellmanford_negative_cycle_detail() {
+        let mut g = Graph::new(7);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        let e1 = g.add_edge(2, 3, -3);
+        let e2 = g.add_edge(3, 4, 1);
+        let e3 = g.add_edge(4, 2, 1);
+        g.add_edge(4, 5, 1);
+        g.add_edge(6, 0, 1);
+        g.add_edge(1, 6, 100);
+        for &spfa in &[false, true] {
+            let mut bf = BellmanFord::new(&g);
+            let ok = if spfa { bf.build_graph_spfa(0) } else { bf.build_graph(0) };
+            assert!(!ok);
+            assert_eq!(bf.neg_inf, vec![false, false, true, true, true, true, false]);
+            let (mut vs, mut es) = bf.negative_cycle().unwrap();
+            let k = vs.iter().position(|&v| v == 2).unwrap();
+            vs.rotate_left(k);
+            es.rotate_left(k);
+            assert_eq!(vs, vec![2, 3, 4]);
+            assert_eq!(es, vec![e1, e2, e3]);
+        }
+    }
+
+    #[test]
+    fn test_bellmanford_spfa() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let n = 20;
+            let mut g = Graph::new(n);
+            for _ in 0..60 {
+                g.add_edge(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(-3..20i64));
+            }
+            let mut a = BellmanFord::new(&g);
+            let mut b = BellmanFord::new(&g);
+            assert_eq!(a.build_graph(0), b.build_graph_spfa(0));
+            assert_eq!(a.neg_inf, b.neg_inf);
+            for v in 0..n {
+                if !a.neg_inf[v] {
+                    assert_eq!(a.dists[v], b.dists[v]);
+                }
+            }
+            if let Some((vs, es)) = a.negative_cycle() {
+                let edges = g.edge_list();
+                let mut sum = 0;
+                for (i, &e) in es.iter().enumerate() {
+                    assert_eq!(edges[e].from, vs[i]);
+                    assert_eq!(edges[e].to, vs[(i + 1) % vs.len()]);
+                    sum += edges[e].cost;
+                }
+                assert!(sum < 0);
+            }
+        }
+    }
 }
\ No newline at end of file
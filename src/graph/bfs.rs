@@ -0,0 +1,152 @@
+use crate::math::algebra::num_trait;
+use crate::graph::base::graph;
+
+pub mod bfs {
+    use super::num_trait::*;
+    use super::graph::*;
+    use std::collections::VecDeque;
+
+    /// Unreachable vertices have distance `usize::MAX`
+    pub const UNREACHABLE: usize = usize::MAX;
+
+    fn run<G: AdjacencyList>(
+        graph: &G,
+        sources: &[usize],
+        weight: impl Fn(&Edge<G::Cost>) -> usize,
+    ) -> (Vec<usize>, Vec<Option<usize>>, Vec<Option<usize>>) {
+        let n = graph.vertex_count();
+        let mut dists = vec![UNREACHABLE; n];
+        let mut parents = vec![None; n];
+        let mut owners = vec![None; n];
+        let mut dq = VecDeque::new();
+        for &s in sources {
+            if dists[s] == 0 { continue }
+            dists[s] = 0;
+            owners[s] = Some(s);
+            dq.push_back(s);
+        }
+        while let Some(v) = dq.pop_front() {
+            for edge in graph.edges(v).iter() {
+                let w = weight(edge);
+                let d = dists[v] + w;
+                if d < dists[edge.to] {
+                    dists[edge.to] = d;
+                    parents[edge.to] = Some(v);
+                    owners[edge.to] = owners[v];
+                    if w == 0 {
+                        dq.push_front(edge.to);
+                    } else {
+                        dq.push_back(edge.to);
+                    }
+                }
+            }
+        }
+        (dists, parents, owners)
+    }
+
+    /// Number of edges from the nearest source, and the parent of each vertex in the BFS tree
+    pub fn bfs<G: AdjacencyList>(graph: &G, sources: &[usize]) -> (Vec<usize>, Vec<Option<usize>>) {
+        let (dists, parents, _) = run(graph, sources, |_| 1);
+        (dists, parents)
+    }
+
+    /// Shortest paths on a graph whose costs are all 0 or 1 in O(V + E)
+    pub fn zero_one_bfs<G>(graph: &G, sources: &[usize]) -> (Vec<usize>, Vec<Option<usize>>)
+    where
+        G: AdjacencyList,
+        G::Cost: Zero + One,
+    {
+        let (dists, parents, _) = run(graph, sources, |edge| {
+            debug_assert!(edge.cost.is_zero() || edge.cost.is_one());
+            if edge.cost.is_zero() { 0 } else { 1 }
+        });
+        (dists, parents)
+    }
+
+    /// Multi-source BFS that also labels each vertex with the source it was reached from
+    /// (ties go to the source listed first). Returns `(dists, owners)`.
+    pub fn bfs_voronoi<G: AdjacencyList>(graph: &G, sources: &[usize]) -> (Vec<usize>, Vec<Option<usize>>) {
+        let (dists, _, owners) = run(graph, sources, |_| 1);
+        (dists, owners)
+    }
+
+    /// Vertices from the root of the BFS tree to `t`, or `None` if `t` was not reached
+    pub fn restore_path(dists: &[usize], parents: &[Option<usize>], t: usize) -> Option<Vec<usize>> {
+        if dists[t] == UNREACHABLE { return None }
+        let mut path = vec![t];
+        let mut v = t;
+        while let Some(p) = parents[v] {
+            path.push(p);
+            v = p;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::graph::*;
+    use super::bfs::*;
+
+    #[test]
+    fn test_bfs() {
+        let mut g = Graph::new(6);
+        g.add_edge_undirected(0, 1, ());
+        g.add_edge_undirected(1, 2, ());
+        g.add_edge_undirected(0, 3, ());
+        g.add_edge_undirected(3, 2, ());
+        g.add_edge(2, 4, ());
+        let (dists, parents) = bfs(&g, &[0]);
+        assert_eq!(dists, vec![0, 1, 2, 1, 3, UNREACHABLE]);
+        assert_eq!(restore_path(&dists, &parents, 4), Some(vec![0, 1, 2, 4]));
+        assert_eq!(restore_path(&dists, &parents, 0), Some(vec![0]));
+        assert_eq!(restore_path(&dists, &parents, 5), None);
+    }
+
+    #[test]
+    fn test_zero_one_bfs() {
+        let mut g = Graph::new(5);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(0, 3, 0);
+        g.add_edge(3, 4, 0);
+        g.add_edge(4, 2, 1);
+        g.add_edge(2, 0, 0);
+        let (dists, parents) = zero_one_bfs(&g, &[0]);
+        assert_eq!(dists, vec![0, 1, 1, 0, 0]);
+        assert_eq!(restore_path(&dists, &parents, 2), Some(vec![0, 3, 4, 2]));
+        let (dists, _) = zero_one_bfs(&g, &[1]);
+        assert_eq!(dists, vec![1, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_zero_one_bfs_matches_dijkstra() {
+        use crate::graph::dijkstra::dijkstra::Dijkstra;
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..20 {
+            let n = 30;
+            let mut g = Graph::new(n);
+            for _ in 0..80 {
+                g.add_edge(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..2i64));
+            }
+            let (dists, _) = zero_one_bfs(&g, &[0]);
+            let mut d = Dijkstra::new(&g);
+            d.build_graph(0);
+            let dists = dists.iter().map(|&x| if x == UNREACHABLE { i64::MAX } else { x as i64 });
+            assert_eq!(dists.collect::<Vec<_>>(), d.dists);
+        }
+    }
+
+    #[test]
+    fn test_bfs_voronoi() {
+        let mut g = Graph::new(7);
+        for i in 0..6 {
+            g.add_edge_undirected(i, i + 1, ());
+        }
+        let (dists, owners) = bfs_voronoi(&g, &[0, 5]);
+        assert_eq!(dists, vec![0, 1, 2, 2, 1, 0, 1]);
+        assert_eq!(owners, vec![Some(0), Some(0), Some(0), Some(5), Some(5), Some(5), Some(5)]);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::graph::base::graph;
+
+pub mod blossom {
+    use super::graph::*;
+    use std::collections::VecDeque;
+
+    struct State<'a, G> {
+        graph: &'a G,
+        mate: Vec<Option<usize>>,
+        parent: Vec<Option<usize>>,
+        base: Vec<usize>,
+        used: Vec<bool>,
+        blossom: Vec<bool>,
+    }
+
+    impl<G: AdjacencyList> State<'_, G> {
+        fn lca(&self, mut a: usize, mut b: usize) -> usize {
+            let mut seen = vec![false; self.mate.len()];
+            loop {
+                a = self.base[a];
+                seen[a] = true;
+                match self.mate[a] {
+                    Some(m) => a = self.parent[m].unwrap(),
+                    None => break,
+                }
+            }
+            loop {
+                b = self.base[b];
+                if seen[b] { return b }
+                b = self.parent[self.mate[b].unwrap()].unwrap();
+            }
+        }
+
+        fn mark_path(&mut self, mut v: usize, b: usize, mut child: usize) {
+            while self.base[v] != b {
+                let m = self.mate[v].unwrap();
+                self.blossom[self.base[v]] = true;
+                self.blossom[self.base[m]] = true;
+                self.parent[v] = Some(child);
+                child = m;
+                v = self.parent[m].unwrap();
+            }
+        }
+
+        /// BFS for an augmenting path from `root`; returns its other end
+        fn find_path(&mut self, root: usize) -> Option<usize> {
+            let n = self.mate.len();
+            self.used = vec![false; n];
+            self.parent = vec![None; n];
+            self.base = (0..n).collect();
+            self.used[root] = true;
+            let mut queue = VecDeque::new();
+            queue.push_back(root);
+            while let Some(v) = queue.pop_front() {
+                for edge in self.graph.edges(v).iter() {
+                    let to = edge.to;
+                    if self.base[v] == self.base[to] || self.mate[v] == Some(to) { continue }
+                    if to == root || self.mate[to].map_or(false, |m| self.parent[m].is_some()) {
+                        // odd cycle: contract the blossom into its base
+                        let b = self.lca(v, to);
+                        self.blossom = vec![false; n];
+                        self.mark_path(v, b, to);
+                        self.mark_path(to, b, v);
+                        for i in 0..n {
+                            if self.blossom[self.base[i]] {
+                                self.base[i] = b;
+                                if !self.used[i] {
+                                    self.used[i] = true;
+                                    queue.push_back(i);
+                                }
+                            }
+                        }
+                    } else if self.parent[to].is_none() {
+                        self.parent[to] = Some(v);
+                        match self.mate[to] {
+                            None => return Some(to),
+                            Some(m) => {
+                                self.used[m] = true;
+                                queue.push_back(m);
+                            }
+                        }
+                    }
+                }
+            }
+            None
+        }
+    }
+
+    /// Maximum matching on a general undirected graph by Edmonds' blossom algorithm in O(V^3)
+    ///
+    /// Every edge must be present in both directions (e.g. added by `add_edge_undirected`).
+    /// Returns the matched pairs `(u, v)` with `u < v`.
+    pub fn general_matching<G: AdjacencyList>(graph: &G) -> Vec<(usize, usize)> {
+        let n = graph.vertex_count();
+        let mut state = State {
+            graph,
+            mate: vec![None; n],
+            parent: vec![],
+            base: vec![],
+            used: vec![],
+            blossom: vec![],
+        };
+        // greedy initial matching
+        for v in 0..n {
+            if state.mate[v].is_some() { continue }
+            if let Some(edge) = graph.edges(v).iter().find(|e| e.to != v && state.mate[e.to].is_none()) {
+                state.mate[v] = Some(edge.to);
+                state.mate[edge.to] = Some(v);
+            }
+        }
+        for root in 0..n {
+            if state.mate[root].is_some() { continue }
+            let mut v = state.find_path(root);
+            while let Some(u) = v {
+                let p = state.parent[u].unwrap();
+                let next = state.mate[p];
+                state.mate[u] = Some(p);
+                state.mate[p] = Some(u);
+                v = next;
+            }
+        }
+        (0..n).filter_map(|u| state.mate[u].filter(|&v| u < v).map(|v| (u, v))).collect()
+    }
+
+    const P: u64 = 1_000_000_007;
+
+    fn pow(mut a: u64, mut e: u64) -> u64 {
+        let mut r = 1;
+        while e > 0 {
+            if e & 1 == 1 { r = r * a % P }
+            a = a * a % P;
+            e >>= 1;
+        }
+        r
+    }
+
+    fn rank(mut a: Vec<Vec<u64>>) -> usize {
+        let n = a.len();
+        let mut r = 0;
+        for c in 0..n {
+            let pivot = match (r..n).find(|&i| a[i][c] != 0) {
+                Some(i) => i,
+                None => continue,
+            };
+            a.swap(r, pivot);
+            let inv = pow(a[r][c], P - 2);
+            let row = a[r].clone();
+            for (i, ai) in a.iter_mut().enumerate() {
+                if i == r || ai[c] == 0 { continue }
+                let f = ai[c] * inv % P;
+                for (x, &y) in ai[c..].iter_mut().zip(row[c..].iter()) {
+                    *x = (*x + P - f * y % P) % P;
+                }
+            }
+            r += 1;
+        }
+        r
+    }
+
+    /// Size of a maximum matching as half the rank of a random Tutte matrix modulo a prime, in O(V^3)
+    ///
+    /// Never overestimates; underestimates with probability at most about `V / 10^9`.
+    /// Useful as an independent check of `general_matching`.
+    pub fn matching_size_tutte<G: AdjacencyList>(graph: &G) -> usize {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        let n = graph.vertex_count();
+        let mut tutte = vec![vec![0; n]; n];
+        for (u, edge) in (0..n).flat_map(|v| graph.edges(v).iter().map(move |e| (v, e))) {
+            let v = edge.to;
+            if u < v {
+                let x = rng.gen_range(1..P);
+                tutte[u][v] = x;
+                tutte[v][u] = P - x;
+            }
+        }
+        rank(tutte) / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::graph::*;
+    use super::blossom::*;
+
+    fn check(g: &Graph<()>, m: &[(usize, usize)]) {
+        let mut used = vec![false; g.0.len()];
+        for &(u, v) in m.iter() {
+            assert!(u < v);
+            assert!(g[u].iter().any(|e| e.to == v));
+            assert!(!used[u] && !used[v]);
+            used[u] = true;
+            used[v] = true;
+        }
+    }
+
+    #[test]
+    fn test_general_matching() {
+        // a 5-cycle with a pendant vertex needs a blossom to be found
+        let mut g = Graph::new(6);
+        for &(u, v) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (4, 5)].iter() {
+            g.add_edge_undirected(u, v, ());
+        }
+        let m = general_matching(&g);
+        check(&g, &m);
+        assert_eq!(m.len(), 3);
+
+        // Petersen graph has a perfect matching
+        let mut g = Graph::new(10);
+        for i in 0..5 {
+            g.add_edge_undirected(i, (i + 1) % 5, ());
+            g.add_edge_undirected(i, i + 5, ());
+            g.add_edge_undirected(i + 5, (i + 2) % 5 + 5, ());
+        }
+        let m = general_matching(&g);
+        check(&g, &m);
+        assert_eq!(m.len(), 5);
+
+        // triangle
+        let mut g = Graph::new(3);
+        g.add_edge_undirected(0, 1, ());
+        g.add_edge_undirected(1, 2, ());
+        g.add_edge_undirected(2, 0, ());
+        assert_eq!(general_matching(&g).len(), 1);
+    }
+
+    #[test]
+    fn test_general_matching_tutte() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(1..16);
+            let m = rng.gen_range(0..n * 2);
+            let mut g = Graph::new(n);
+            for _ in 0..m {
+                g.add_edge_undirected(rng.gen_range(0..n), rng.gen_range(0..n), ());
+            }
+            let matching = general_matching(&g);
+            check(&g, &matching);
+            assert_eq!(matching.len(), matching_size_tutte(&g));
+        }
+    }
+}
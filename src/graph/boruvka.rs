@@ -0,0 +1,110 @@
+use crate::math::algebra::num_trait;
+use crate::data_structure::unionfind::unionfind;
+
+pub mod boruvka {
+    use super::num_trait::*;
+    use super::unionfind::UnionFind;
+    use std::cmp::*;
+
+    /// Minimum spanning forest by Borůvka's algorithm, for graphs given implicitly
+    ///
+    /// Each round calls `cheapest(comp)`, where `comp[v]` is the component of `v`, and expects
+    /// for every vertex `v` its cheapest edge `(to, cost)` with `comp[to] != comp[v]` (or `None`).
+    /// There are O(log V) rounds. Returns the total weight and the chosen edges `(u, v, cost)`.
+    pub fn boruvka<T, F>(n: usize, mut cheapest: F) -> (T, Vec<(usize, usize, T)>)
+    where
+        T: AbelGroup + Ord,
+        F: FnMut(&[usize]) -> Vec<Option<(usize, T)>>,
+    {
+        let mut uft = UnionFind::new(n);
+        let mut weight = T::zero();
+        let mut edges = vec![];
+        loop {
+            let mut id = vec![usize::MAX; n];
+            let mut comp = vec![0; n];
+            let mut k = 0;
+            for (v, c) in comp.iter_mut().enumerate() {
+                let r = uft.find(v);
+                if id[r] == usize::MAX {
+                    id[r] = k;
+                    k += 1;
+                }
+                *c = id[r];
+            }
+            if k <= 1 { break }
+            // break ties by the endpoints so that every component picks consistently
+            let mut best: Vec<Option<(T, usize, usize)>> = vec![None; k];
+            for (v, cand) in cheapest(&comp).into_iter().enumerate() {
+                if let Some((to, cost)) = cand {
+                    debug_assert!(comp[v] != comp[to]);
+                    let key = Some((cost, min(v, to), max(v, to)));
+                    let b = &mut best[comp[v]];
+                    if b.is_none() || key < *b {
+                        *b = key;
+                    }
+                }
+            }
+            let mut merged = false;
+            for (cost, u, v) in best.into_iter().flatten() {
+                if uft.unite(u, v) {
+                    weight += cost;
+                    edges.push((u, v, cost));
+                    merged = true;
+                }
+            }
+            if !merged { break }
+        }
+        (weight, edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::boruvka::*;
+    use crate::graph::base::graph::Graph;
+    use crate::graph::kruskal::kruskal::Kruskal;
+
+    #[test]
+    fn test_boruvka_graph() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(1..30);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..60) {
+                g.add_edge_undirected(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..10i64));
+            }
+            let (w, edges) = boruvka(n, |comp| {
+                (0..n).map(|v| {
+                    g[v].iter().filter(|e| comp[e.to] != comp[v]).map(|e| (e.to, e.cost)).min_by_key(|p| p.1)
+                }).collect()
+            });
+            let mut kr = Kruskal::new(&g);
+            assert_eq!(w, kr.build());
+            assert_eq!(edges.len(), kr.edges().len());
+        }
+    }
+
+    #[test]
+    fn test_boruvka_xor() {
+        // complete graph with cost a[u] xor a[v], answered by brute force per round
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        let n = 60;
+        let a = (0..n).map(|_| rng.gen_range(0..1 << 10)).collect::<Vec<i64>>();
+        let (w, edges) = boruvka(n, |comp| {
+            (0..n).map(|v| {
+                (0..n).filter(|&u| comp[u] != comp[v]).map(|u| (u, a[u] ^ a[v])).min_by_key(|p| p.1)
+            }).collect()
+        });
+        let mut g = Graph::new(n);
+        for u in 0..n {
+            for v in u + 1..n {
+                g.add_edge_undirected(u, v, a[u] ^ a[v]);
+            }
+        }
+        let mut kr = Kruskal::new(&g);
+        assert_eq!(w, kr.build());
+        assert_eq!(edges.len(), n - 1);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::graph::base::graph;
+
+pub mod csr_graph {
+    use super::graph::*;
+    use std::iter::once;
+    use std::ops::*;
+
+    /// Graph in compressed sparse row form
+    ///
+    /// The edges leaving `v` are stored contiguously in `edges[start[v]..start[v + 1]]`,
+    /// in the order they were given.
+    #[derive(Debug, Clone)]
+    pub struct CsrGraph<T> {
+        start: Vec<usize>,
+        edges: Vec<Edge<T>>,
+        directed: bool,
+    }
+
+    impl<T: Clone> CsrGraph<T> {
+        fn build<I>(n: usize, m: usize, iter: I, directed: bool) -> Self
+        where
+            I: Iterator<Item = (usize, usize, T, usize)> + Clone
+        {
+            let mut start = vec![0; n + 1];
+            for (from, _, _, _) in iter.clone() {
+                start[from + 1] += 1;
+            }
+            for i in 0..n {
+                start[i + 1] += start[i];
+            }
+            let mut pos = start.clone();
+            let mut slots = vec![None; m];
+            for (from, to, cost, id) in iter {
+                slots[pos[from]] = Some(Edge::with_id(from, to, cost, id));
+                pos[from] += 1;
+            }
+            let edges = slots.into_iter().map(Option::unwrap).collect();
+            Self { start, edges, directed }
+        }
+
+        /// Directed graph from `(from, to, cost)`; the `i`-th edge gets id `i`.
+        pub fn new(n: usize, edges: &[(usize, usize, T)]) -> Self {
+            let iter = edges.iter().enumerate().map(|(i, (u, v, c))| (*u, *v, c.clone(), i));
+            Self::build(n, edges.len(), iter, true)
+        }
+
+        /// Undirected graph from `(u, v, cost)`; both directions of the `i`-th edge get id `i`.
+        pub fn new_undirected(n: usize, edges: &[(usize, usize, T)]) -> Self {
+            let iter = edges.iter().enumerate().flat_map(|(i, (u, v, c))| {
+                once((*u, *v, c.clone(), i)).chain(once((*v, *u, c.clone(), i)))
+            });
+            Self::build(n, edges.len() * 2, iter, false)
+        }
+
+        /// Graph with every edge reversed, keeping edge ids.
+        pub fn reverse(&self) -> Self {
+            let n = self.len();
+            let iter = self.edges.iter().map(|e| (e.to, e.from, e.cost.clone(), e.id));
+            Self::build(n, self.edges.len(), iter, self.directed)
+        }
+    }
+
+    impl<T> CsrGraph<T> {
+        pub fn len(&self) -> usize {
+            self.start.len() - 1
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        pub fn is_directed(&self) -> bool {
+            self.directed
+        }
+
+        /// Number of stored (directed) edges
+        pub fn edge_count(&self) -> usize {
+            self.edges.len()
+        }
+
+        pub fn degree(&self, v: usize) -> usize {
+            self.start[v + 1] - self.start[v]
+        }
+
+        pub fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+            self[v].iter().map(|e| e.to)
+        }
+
+        /// Ids of the edges leaving `v`, parallel to `self[v]`
+        pub fn edge_ids(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+            self[v].iter().map(|e| e.id)
+        }
+    }
+
+    impl<T> Index<usize> for CsrGraph<T> {
+        type Output = [Edge<T>];
+        fn index(&self, index: usize) -> &Self::Output {
+            &self.edges[self.start[index]..self.start[index + 1]]
+        }
+    }
+
+    impl<T> AdjacencyList for CsrGraph<T> {
+        type Cost = T;
+        fn vertex_count(&self) -> usize {
+            self.len()
+        }
+        fn edges(&self, v: usize) -> &[Edge<T>] {
+            &self[v]
+        }
+    }
+
+    impl<'a, T: Clone> From<&'a Graph<T>> for CsrGraph<T> {
+        fn from(graph: &'a Graph<T>) -> Self {
+            let n = graph.0.len();
+            let m = graph.0.iter().map(|edges| edges.len()).sum();
+            let iter = graph.0.iter().enumerate().flat_map(|(v, edges)| {
+                edges.iter().map(move |e| (v, e.to, e.cost.clone(), e.id))
+            });
+            Self::build(n, m, iter, true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::graph::*;
+    use super::csr_graph::*;
+
+    #[test]
+    fn test_csr_graph_directed() {
+        let g = CsrGraph::new(4, &[(0, 1, 5), (2, 0, 3), (0, 2, 1), (1, 3, 2)]);
+        assert_eq!(g.len(), 4);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(g.edge_ids(0).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(g[0][1].cost, 1);
+        assert_eq!(g.degree(3), 0);
+        let r = g.reverse();
+        assert_eq!(r.neighbors(0).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(r.edge_ids(0).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(r.neighbors(3).collect::<Vec<_>>(), vec![1]);
+        assert_eq!((r[3][0].from, r[3][0].id), (3, 3));
+    }
+
+    #[test]
+    fn test_csr_graph_undirected() {
+        let g = CsrGraph::new_undirected(3, &[(0, 1, 1), (1, 2, 2)]);
+        assert!(!g.is_directed());
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.neighbors(1).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(g.edge_ids(1).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(g[2][0].cost, 2);
+    }
+
+    #[test]
+    fn test_csr_graph_from_graph() {
+        let mut g = Graph::new(3);
+        g.add_edge(0, 1, 1);
+        g.add_edge(2, 1, 3);
+        g.add_edge(0, 2, 2);
+        let c = CsrGraph::from(&g);
+        for v in 0..3 {
+            assert_eq!(c.edges(v), g.edges(v));
+        }
+        assert_eq!(c.edge_ids(0).collect::<Vec<_>>(), vec![0, 2]);
+    }
+}
@@ -9,46 +9,94 @@ pub mod dijkstra {
     use std::collections::BinaryHeap;
 
     #[derive(Debug, Clone)]
-    pub struct Dijkstra<'a, T> {
-        graph: &'a Graph<T>,
+    pub struct Dijkstra<'a, T, G = Graph<T>> {
+        graph: &'a G,
         pub dists: Vec<T>,
-        backs: Vec<isize>,
+        /// previous vertex and the edge taken from it
+        parents: Vec<Option<(usize, &'a Edge<T>)>>,
     }
 
-    impl<'a, T: AbelGroup + Bounded + Eq + Ord> Dijkstra<'a, T> {
-        pub fn new(graph: &'a Graph<T>) -> Self {
-            let n = graph.0.len();
+    impl<'a, T, G> Dijkstra<'a, T, G>
+    where
+        T: AbelGroup + Bounded + Ord,
+        G: AdjacencyList<Cost = T>,
+    {
+        pub fn new(graph: &'a G) -> Self {
+            let n = graph.vertex_count();
             let dists = vec![T::max_value(); n];
-            let backs = vec![-1; n];
-            Self { graph, dists, backs }
+            let parents = vec![None; n];
+            Self { graph, dists, parents }
         }
 
-        pub fn build_graph(&mut self, s: usize) {
+        fn run(&mut self, sources: &[usize], target: Option<usize>) {
+            let graph = self.graph;
             let mut pq = BinaryHeap::new();
-            self.dists[s] = T::zero();
-            pq.push(Edge::new(s, self.dists[s]));
-            while !pq.is_empty() {
-                let p = pq.pop().unwrap();
-                let v = p.to;
-                if self.dists[v] < p.cost { continue; }
-                for edge in self.graph[v].iter() {
+            for &s in sources {
+                self.dists[s] = T::zero();
+                pq.push(Reverse((self.dists[s], s)));
+            }
+            while let Some(Reverse((d, v))) = pq.pop() {
+                if self.dists[v] < d { continue; }
+                if target == Some(v) { break; }
+                for edge in graph.edges(v).iter() {
                     if self.dists[edge.to] > self.dists[v] + edge.cost {
                         self.dists[edge.to] = self.dists[v] + edge.cost;
-                        self.backs[edge.to] = v as isize;
-                        pq.push(Edge::new(edge.to, self.dists[edge.to]))
+                        self.parents[edge.to] = Some((v, edge));
+                        pq.push(Reverse((self.dists[edge.to], edge.to)))
                     }
                 }
             }
         }
 
+        pub fn build_graph(&mut self, s: usize) {
+            self.run(&[s], None);
+        }
+
+        /// Distances from the nearest of `sources`
+        pub fn build_multi(&mut self, sources: &[usize]) {
+            self.run(sources, None);
+        }
+
+        /// Stops as soon as the distance to `t` is settled.
+        ///
+        /// Only `dists[t]` and `path_to(t)` (and those of vertices settled earlier) are reliable.
+        pub fn build_until(&mut self, s: usize, t: usize) {
+            self.run(&[s], Some(t));
+        }
+
+        /// The last edge of a shortest path to `v`, or `None` for sources and unreachable vertices
+        pub fn parent_edge(&self, v: usize) -> Option<&'a Edge<T>> {
+            self.parents[v].map(|(_, e)| e)
+        }
+
+        /// The vertex before `v` on a shortest path to `v`
+        pub fn parent(&self, v: usize) -> Option<usize> {
+            self.parents[v].map(|(u, _)| u)
+        }
+
+        /// Vertices of a shortest path from a source to `t`, both ends included
+        pub fn path_to(&self, t: usize) -> Option<Vec<usize>> {
+            if self.dists[t] == T::max_value() { return None }
+            let mut path = vec![t];
+            let mut v = t;
+            while let Some((u, _)) = self.parents[v] {
+                v = u;
+                path.push(v);
+            }
+            path.reverse();
+            Some(path)
+        }
+
+        /// Path to `to` **excluding the source**; kept for compatibility, prefer `path_to`.
         pub fn restore(&self, mut to: isize) -> Vec<isize> {
+            let back = |v: isize| self.parents[v as usize].map_or(-1, |(u, _)| u as isize);
             let mut path = vec![];
-            if self.backs[to as usize] < 0 {
+            if back(to) < 0 {
                 path
             } else {
                 while to > 0 {
                     path.push(to);
-                    to = self.backs[to as usize];
+                    to = back(to);
                 }
                 path.reverse();
                 path
@@ -91,4 +139,62 @@ mod tests {
         assert_eq!(g.dists, vec![3, 0, 2, std::i32::MAX]);
         assert_eq!(g.restore(3), vec![]);
     }
+
+    #[test]
+    fn test_dijkstra_path_to() {
+        let mut g = Graph::new(5);
+        g.add_edge(1, 0, 2);
+        g.add_edge(0, 2, 1);
+        g.add_edge(1, 2, 5);
+        let id = g.add_edge(2, 3, 1);
+        g.add_edge(4, 3, 1);
+        let mut d = Dijkstra::new(&g);
+        d.build_graph(1);
+        assert_eq!(d.path_to(3), Some(vec![1, 0, 2, 3]));
+        assert_eq!(d.path_to(1), Some(vec![1]));
+        assert_eq!(d.path_to(4), None);
+        assert_eq!(d.parent_edge(3).map(|e| e.id), Some(id));
+        assert_eq!(d.parent_edge(1), None);
+    }
+
+    #[test]
+    fn test_dijkstra_multi_source() {
+        let mut g = Graph::new(6);
+        for i in 0..5 {
+            g.add_edge_undirected(i, i + 1, 1);
+        }
+        let mut d = Dijkstra::new(&g);
+        d.build_multi(&[0, 5]);
+        assert_eq!(d.dists, vec![0, 1, 2, 2, 1, 0]);
+        assert_eq!(d.path_to(4), Some(vec![5, 4]));
+        assert_eq!(d.path_to(1), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_dijkstra_until() {
+        let mut g = Graph::new(4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(2, 3, 1);
+        g.add_edge(0, 3, 10);
+        let mut d = Dijkstra::new(&g);
+        d.build_until(0, 1);
+        assert_eq!(d.dists[1], 1);
+        assert_eq!(d.path_to(1), Some(vec![0, 1]));
+        assert_eq!(d.dists[2], i32::MAX);
+        assert_eq!(d.dists[3], 10);
+    }
+
+    #[test]
+    fn test_dijkstra_csr() {
+        use crate::graph::csr_graph::csr_graph::CsrGraph;
+        let g = CsrGraph::new(4, &[(0, 1, 1), (0, 2, 4), (1, 2, 2), (2, 3, 1), (1, 3, 5)]);
+        let mut d = Dijkstra::new(&g);
+        d.build_graph(0);
+        assert_eq!(d.dists, vec![0, 1, 3, 4]);
+        let g = CsrGraph::new_undirected(4, &[(0, 1, 1), (1, 2, 2), (2, 3, 1)]);
+        let mut d = Dijkstra::new(&g);
+        d.build_graph(3);
+        assert_eq!(d.dists, vec![4, 3, 1, 0]);
+    }
 }
\ No newline at end of file
@@ -13,11 +13,23 @@ pub mod dinic {
         rev: i64,
     }
 
+    /// State of an edge added by `add_flow`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct FlowEdge<T> {
+        pub from: usize,
+        pub to: usize,
+        pub cap: T,
+        pub flow: T,
+    }
+
+    /// `max_flow` continues from the current flow, so after increasing capacities
+    /// with `change_edge` or adding edges it returns only the additional flow.
     #[derive(Debug)]
     pub struct Dinic<T> {
         graph: Vec<Vec<Edge<T>>>,
         min_cost: Vec<T>,
         iter: Vec<usize>,
+        pos: Vec<(usize, usize)>,
     }
 
     impl<T: Ring + Bounded + Eq + Ord> Dinic<T> {
@@ -25,14 +37,66 @@ pub mod dinic {
             let graph = vec![vec![]; size];
             let min_cost = vec![];
             let iter = vec![];
-            Self { graph, min_cost, iter }
+            let pos = vec![];
+            Self { graph, min_cost, iter, pos }
         }
 
-        pub fn add_flow(&mut self, from: usize, to: usize, cap: T) {
-            let len_to = self.graph[to].len() as i64;
+        /// Adds an edge and returns its id
+        pub fn add_flow(&mut self, from: usize, to: usize, cap: T) -> usize {
+            let id = self.pos.len();
+            // for a self loop the reverse edge lands right after the forward one
+            let len_to = (self.graph[to].len() + usize::from(from == to)) as i64;
             let len_from = self.graph[from].len() as i64;
+            self.pos.push((from, len_from as usize));
             self.graph[from].push(Edge { to, cap, rev: len_to });
             self.graph[to].push(Edge { to: from, cap: T::zero(), rev: len_from });
+            id
+        }
+
+        /// Current flow on the edge `id`
+        pub fn flow(&self, id: usize) -> T {
+            let (from, i) = self.pos[id];
+            let edge = self.graph[from][i];
+            self.graph[edge.to][edge.rev as usize].cap
+        }
+
+        pub fn get_edge(&self, id: usize) -> FlowEdge<T> {
+            let (from, i) = self.pos[id];
+            let edge = self.graph[from][i];
+            let flow = self.graph[edge.to][edge.rev as usize].cap;
+            FlowEdge { from, to: edge.to, cap: edge.cap + flow, flow }
+        }
+
+        pub fn edges(&self) -> Vec<FlowEdge<T>> {
+            (0..self.pos.len()).map(|id| self.get_edge(id)).collect()
+        }
+
+        /// Sets the capacity and the flow of the edge `id`.
+        /// Flow conservation is up to the caller.
+        pub fn change_edge(&mut self, id: usize, cap: T, flow: T) {
+            assert!(T::zero() <= flow && flow <= cap);
+            let (from, i) = self.pos[id];
+            let edge = self.graph[from][i];
+            self.graph[from][i].cap = cap - flow;
+            self.graph[edge.to][edge.rev as usize].cap = flow;
+        }
+
+        /// Vertices reachable from `s` in the residual graph.
+        /// After `max_flow(s, t)` they form the source side of a minimum cut.
+        pub fn min_cut(&self, s: usize) -> Vec<bool> {
+            let mut visited = vec![false; self.graph.len()];
+            let mut queue = VecDeque::new();
+            visited[s] = true;
+            queue.push_back(s);
+            while let Some(p) = queue.pop_front() {
+                for edge in self.graph[p].iter() {
+                    if edge.cap > T::zero() && !visited[edge.to] {
+                        visited[edge.to] = true;
+                        queue.push_back(edge.to);
+                    }
+                }
+            }
+            visited
         }
 
         fn bfs(&mut self, s: usize, t: usize) -> bool {
@@ -101,4 +165,94 @@ mod tests {
         let mf = g.max_flow(0, 3);
         assert_eq!(mf, 3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_edge_flow() {
+        let mut g = Dinic::new(3);
+        let e0 = g.add_flow(0, 1, 5);
+        let e1 = g.add_flow(1, 2, 3);
+        let e2 = g.add_flow(0, 2, 1);
+        assert_eq!(g.max_flow(0, 2), 4);
+        assert_eq!((g.flow(e0), g.flow(e1), g.flow(e2)), (3, 3, 1));
+        assert_eq!(g.get_edge(e1), FlowEdge { from: 1, to: 2, cap: 3, flow: 3 });
+    }
+
+    #[test]
+    fn test_min_cut() {
+        let mut g = Dinic::new(6);
+        let caps = [(0, 1, 3), (0, 2, 3), (1, 2, 2), (1, 3, 3), (2, 4, 2), (3, 4, 4), (3, 5, 2), (4, 5, 3)];
+        for &(u, v, c) in caps.iter() {
+            g.add_flow(u, v, c);
+        }
+        assert_eq!(g.max_flow(0, 5), 5);
+        let cut = g.min_cut(0);
+        assert!(cut[0] && !cut[5]);
+        let value = g.edges().iter().filter(|e| cut[e.from] && !cut[e.to]).map(|e| e.cap).sum::<i64>();
+        assert_eq!(value, 5);
+        for e in g.edges() {
+            if cut[e.from] && !cut[e.to] {
+                assert_eq!(e.flow, e.cap);
+            }
+        }
+    }
+
+    #[test]
+    fn test_incremental_max_flow() {
+        let mut g = Dinic::new(4);
+        let e0 = g.add_flow(0, 1, 1);
+        g.add_flow(1, 3, 5);
+        g.add_flow(0, 2, 2);
+        let e3 = g.add_flow(2, 3, 1);
+        assert_eq!(g.max_flow(0, 3), 2);
+        let f = g.get_edge(e0).flow;
+        g.change_edge(e0, 4, f);
+        assert_eq!(g.max_flow(0, 3), 3);
+        let f = g.get_edge(e3).flow;
+        g.change_edge(e3, 2, f);
+        assert_eq!(g.max_flow(0, 3), 1);
+        g.add_flow(0, 3, 10);
+        assert_eq!(g.max_flow(0, 3), 10);
+        assert_eq!(g.edges().iter().filter(|e| e.from == 0).map(|e| e.flow).sum::<i64>(), 16);
+    }
+
+    #[test]
+    fn test_incremental_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..30 {
+            let n = 10;
+            let mut caps = vec![];
+            let mut g = Dinic::new(n);
+            let mut total = 0;
+            for _ in 0..30 {
+                let (u, v, c) = (rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..10i64));
+                caps.push((u, v, c));
+                g.add_flow(u, v, c);
+            }
+            for _ in 0..10 {
+                let id = rng.gen_range(0..caps.len());
+                caps[id].2 += rng.gen_range(1..5);
+                let f = g.get_edge(id).flow;
+                g.change_edge(id, caps[id].2, f);
+                total += g.max_flow(0, n - 1);
+                let mut fresh = Dinic::new(n);
+                for &(u, v, c) in caps.iter() {
+                    fresh.add_flow(u, v, c);
+                }
+                assert_eq!(total, fresh.max_flow(0, n - 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let mut g = Dinic::new(2);
+        let e0 = g.add_flow(0, 0, 5);
+        let e1 = g.add_flow(0, 1, 3);
+        assert_eq!(g.get_edge(e0), FlowEdge { from: 0, to: 0, cap: 5, flow: 0 });
+        assert_eq!(g.max_flow(0, 1), 3);
+        g.change_edge(e0, 7, 2);
+        assert_eq!(g.get_edge(e0), FlowEdge { from: 0, to: 0, cap: 7, flow: 2 });
+        assert_eq!(g.get_edge(e1), FlowEdge { from: 0, to: 1, cap: 3, flow: 3 });
+    }
+}
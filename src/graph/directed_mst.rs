@@ -0,0 +1,217 @@
+use crate::math::algebra::num_trait;
+use crate::graph::base::graph;
+use crate::data_structure::skew_heap::skew_heap;
+
+pub mod directed_mst {
+    use super::num_trait::*;
+    use super::graph::*;
+    use super::skew_heap::LazySkewHeap;
+    use std::mem::take;
+
+    /// Union-find without path compression whose unions can be undone
+    struct RollbackUnionFind {
+        parent: Vec<usize>,
+        sizes: Vec<usize>,
+        history: Vec<(usize, usize)>,
+    }
+
+    impl RollbackUnionFind {
+        fn new(n: usize) -> Self {
+            Self { parent: (0..n).collect(), sizes: vec![1; n], history: vec![] }
+        }
+
+        fn find(&self, mut x: usize) -> usize {
+            while self.parent[x] != x {
+                x = self.parent[x];
+            }
+            x
+        }
+
+        fn unite(&mut self, x: usize, y: usize) -> bool {
+            let (mut x, mut y) = (self.find(x), self.find(y));
+            if x == y { return false }
+            if self.sizes[x] < self.sizes[y] {
+                std::mem::swap(&mut x, &mut y);
+            }
+            self.parent[y] = x;
+            self.sizes[x] += self.sizes[y];
+            self.history.push((x, y));
+            true
+        }
+
+        fn time(&self) -> usize {
+            self.history.len()
+        }
+
+        fn rollback(&mut self, time: usize) {
+            while self.history.len() > time {
+                let (x, y) = self.history.pop().unwrap();
+                self.parent[y] = y;
+                self.sizes[x] -= self.sizes[y];
+            }
+        }
+    }
+
+    /// Minimum spanning arborescence rooted at `root` (Chu-Liu/Edmonds, Tarjan's O(E log V))
+    ///
+    /// Returns the total cost and the edge entering each vertex (`None` for `root`),
+    /// or `None` if some vertex is unreachable from `root`.
+    pub fn directed_mst<T>(graph: &Graph<T>, root: usize) -> Option<(T, Vec<Option<&Edge<T>>>)>
+    where
+        T: AbelGroup + Ord,
+    {
+        let n = graph.0.len();
+        let edges = graph.0.iter().flatten().collect::<Vec<_>>();
+        let mut heaps = (0..n).map(|_| LazySkewHeap::new()).collect::<Vec<_>>();
+        for (i, edge) in edges.iter().enumerate() {
+            heaps[edge.to].push(edge.cost, i);
+        }
+        let mut uf = RollbackUnionFind::new(n);
+        let mut seen = vec![usize::MAX; n];
+        seen[root] = root;
+        let mut path = vec![0; n];
+        let mut queue = vec![0; n];
+        let mut incoming = vec![None; n];
+        // contracted cycles: (representative, union-find time, edges on the cycle)
+        let mut cycles = vec![];
+        let mut total = T::zero();
+        for s in 0..n {
+            let (mut u, mut qi) = (s, 0);
+            while seen[u] == usize::MAX {
+                let (w, e) = heaps[u].pop()?;
+                heaps[u].add_all(-w);
+                queue[qi] = e;
+                path[qi] = u;
+                qi += 1;
+                seen[u] = s;
+                total += w;
+                u = uf.find(edges[e].from);
+                if seen[u] == s {
+                    let mut cycle = LazySkewHeap::new();
+                    let (end, time) = (qi, uf.time());
+                    loop {
+                        qi -= 1;
+                        let w = path[qi];
+                        cycle = cycle + take(&mut heaps[w]);
+                        if !uf.unite(u, w) { break }
+                    }
+                    u = uf.find(u);
+                    heaps[u] = cycle;
+                    seen[u] = usize::MAX;
+                    cycles.push((u, time, queue[qi..end].to_vec()));
+                }
+            }
+            for &e in queue[..qi].iter() {
+                incoming[uf.find(edges[e].to)] = Some(e);
+            }
+        }
+        // expand cycles from the outermost: the edge entering a cycle replaces one of its edges
+        for (u, time, cycle) in cycles.into_iter().rev() {
+            uf.rollback(time);
+            let entering = incoming[u];
+            for e in cycle {
+                incoming[uf.find(edges[e].to)] = Some(e);
+            }
+            if let Some(e) = entering {
+                incoming[uf.find(edges[e].to)] = Some(e);
+            }
+        }
+        let parents = (0..n).map(|v| {
+            if v == root { None } else { incoming[v].map(|e| edges[e]) }
+        }).collect();
+        Some((total, parents))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::graph::*;
+    use super::directed_mst::*;
+
+    #[test]
+    fn test_directed_mst() {
+        let mut g = Graph::new(4);
+        g.add_edge(0, 1, 3);
+        g.add_edge(0, 2, 2);
+        g.add_edge(2, 0, 1);
+        g.add_edge(2, 3, 1);
+        g.add_edge(3, 0, 1);
+        g.add_edge(3, 1, 5);
+        let (cost, parents) = directed_mst(&g, 0).unwrap();
+        assert_eq!(cost, 6);
+        let ids = parents.iter().map(|e| e.map(|e| e.id)).collect::<Vec<_>>();
+        assert_eq!(ids, vec![None, Some(0), Some(1), Some(3)]);
+
+        let mut g = Graph::new(3);
+        g.add_edge(0, 1, 1);
+        g.add_edge(2, 1, 1);
+        assert!(directed_mst(&g, 0).is_none());
+    }
+
+    #[test]
+    fn test_directed_mst_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..300 {
+            let n = rng.gen_range(1..6);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..12) {
+                g.add_edge(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(-5..10i64));
+            }
+            let root = rng.gen_range(0..n);
+
+            // brute force: choose an incoming edge for every other vertex
+            let incoming = (0..n).map(|v| g.0.iter().flatten().filter(|e| e.to == v).collect::<Vec<_>>()).collect::<Vec<_>>();
+            let mut best = None;
+            let mut choice = vec![0; n];
+            'outer: loop {
+                let mut ok = true;
+                let mut cost = 0;
+                for v in 0..n {
+                    if v == root { continue }
+                    if incoming[v].is_empty() { ok = false; break }
+                    cost += incoming[v][choice[v]].cost;
+                }
+                if ok {
+                    let reaches_root = (0..n).all(|mut v| {
+                        for _ in 0..n {
+                            if v == root { return true }
+                            v = incoming[v][choice[v]].from;
+                        }
+                        v == root
+                    });
+                    if reaches_root && best.map_or(true, |b| cost < b) {
+                        best = Some(cost);
+                    }
+                } else {
+                    break;
+                }
+                for v in 0..n {
+                    if v == root { continue }
+                    choice[v] += 1;
+                    if choice[v] < incoming[v].len() { continue 'outer }
+                    choice[v] = 0;
+                }
+                break;
+            }
+
+            match directed_mst(&g, root) {
+                None => assert_eq!(best, None),
+                Some((cost, parents)) => {
+                    assert_eq!(Some(cost), best);
+                    assert_eq!(parents.iter().flatten().map(|e| e.cost).sum::<i64>(), cost);
+                    for v in 0..n {
+                        let mut u = v;
+                        for _ in 0..n {
+                            if u == root { break }
+                            let e = parents[u].unwrap();
+                            assert_eq!(e.to, u);
+                            u = e.from;
+                        }
+                        assert_eq!(u, root);
+                    }
+                }
+            }
+        }
+    }
+}
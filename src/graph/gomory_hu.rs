@@ -0,0 +1,143 @@
+use crate::math::algebra::num_trait;
+use crate::graph::base::graph;
+use crate::graph::dinic::dinic;
+
+pub mod gomory_hu {
+    use super::num_trait::*;
+    use super::graph::*;
+    use super::dinic::Dinic;
+    use std::cmp::*;
+
+    /// Gomory-Hu tree (Gusfield's algorithm) of an undirected graph
+    ///
+    /// Built with V - 1 max flow computations. The minimum `s`-`t` cut of the graph equals
+    /// the minimum edge weight on the `s`-`t` path of the tree, answered in O(log V).
+    #[derive(Debug, Clone)]
+    pub struct GomoryHu<T> {
+        parent: Vec<usize>,
+        weight: Vec<T>,
+        depth: Vec<usize>,
+        /// `up[k][v]`: `2^k`-th ancestor and the minimum weight on the way
+        up: Vec<Vec<(usize, T)>>,
+    }
+
+    impl<T: Ring + Bounded + Eq + Ord> GomoryHu<T> {
+        pub fn new(graph: &Graph<T>) -> Self {
+            let n = graph.0.len();
+            let edges = graph.edge_list();
+            let mut parent = vec![0; n];
+            let mut weight = vec![T::max_value(); n];
+            for s in 1..n {
+                let t = parent[s];
+                let mut dinic = Dinic::new(n);
+                for edge in edges.iter() {
+                    dinic.add_flow(edge.from, edge.to, edge.cost);
+                    dinic.add_flow(edge.to, edge.from, edge.cost);
+                }
+                weight[s] = dinic.max_flow(s, t);
+                let cut = dinic.min_cut(s);
+                for v in s + 1..n {
+                    if cut[v] && parent[v] == t {
+                        parent[v] = s;
+                    }
+                }
+            }
+            // parent[v] < v, so depths can be filled in index order
+            let mut depth = vec![0; n];
+            for v in 1..n {
+                depth[v] = depth[parent[v]] + 1;
+            }
+            let mut up = vec![(0..n).map(|v| (parent[v], weight[v])).collect::<Vec<_>>()];
+            while 1 << up.len() < n {
+                let last = up.last().unwrap();
+                let next = (0..n).map(|v| {
+                    let (p, w) = last[v];
+                    (last[p].0, min(w, last[p].1))
+                }).collect();
+                up.push(next);
+            }
+            Self { parent, weight, depth, up }
+        }
+
+        /// Minimum cut between `s` and `t` (`T::max_value()` if `s == t`)
+        pub fn min_cut(&self, mut s: usize, mut t: usize) -> T {
+            let mut ret = T::max_value();
+            if self.depth[s] < self.depth[t] {
+                std::mem::swap(&mut s, &mut t);
+            }
+            let diff = self.depth[s] - self.depth[t];
+            for (k, up) in self.up.iter().enumerate() {
+                if diff >> k & 1 == 1 {
+                    ret = min(ret, up[s].1);
+                    s = up[s].0;
+                }
+            }
+            if s == t { return ret }
+            for up in self.up.iter().rev() {
+                if up[s].0 != up[t].0 {
+                    ret = min(ret, min(up[s].1, up[t].1));
+                    s = up[s].0;
+                    t = up[t].0;
+                }
+            }
+            min(ret, min(self.weight[s], self.weight[t]))
+        }
+
+        /// The tree as an undirected graph
+        pub fn tree(&self) -> Graph<T> {
+            let mut tree = Graph::new(self.parent.len());
+            for v in 1..self.parent.len() {
+                tree.add_edge_undirected(v, self.parent[v], self.weight[v]);
+            }
+            tree
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::graph::*;
+    use super::gomory_hu::*;
+    use super::dinic::Dinic;
+
+    #[test]
+    fn test_gomory_hu() {
+        let mut g = Graph::new(6);
+        let edges = [(0, 1, 1), (0, 2, 7), (1, 2, 1), (1, 3, 3), (1, 4, 2), (2, 4, 4), (3, 4, 1), (3, 5, 6), (4, 5, 2)];
+        for &(u, v, c) in edges.iter() {
+            g.add_edge_undirected(u, v, c);
+        }
+        let gh = GomoryHu::new(&g);
+        assert_eq!(gh.min_cut(0, 5), 6);
+        assert_eq!(gh.min_cut(5, 3), 8);
+        assert_eq!(gh.min_cut(2, 0), 8);
+        assert_eq!(gh.min_cut(4, 4), i64::MAX);
+        let tree = gh.tree();
+        assert_eq!(tree.edge_count(), 5);
+    }
+
+    #[test]
+    fn test_gomory_hu_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..30 {
+            let n = rng.gen_range(1..12);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..30) {
+                g.add_edge_undirected(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..10i64));
+            }
+            let gh = GomoryHu::new(&g);
+            for s in 0..n {
+                for t in 0..n {
+                    if s == t { continue }
+                    let mut dinic = Dinic::new(n);
+                    for e in g.edge_list() {
+                        dinic.add_flow(e.from, e.to, e.cost);
+                        dinic.add_flow(e.to, e.from, e.cost);
+                    }
+                    assert_eq!(gh.min_cut(s, t), dinic.max_flow(s, t));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use crate::math::algebra::num_trait;
+
+pub mod grid {
+    use super::num_trait::*;
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, VecDeque};
+    use std::ops::*;
+
+    pub const DIR4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+    pub const DIR8: [(isize, isize); 8] = [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+    /// Unreachable cells have distance `usize::MAX`
+    pub const UNREACHABLE: usize = usize::MAX;
+
+    /// `H x W` grid, typically read row by row with `IO::chars`
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Grid<T> {
+        h: usize,
+        w: usize,
+        cells: Vec<Vec<T>>,
+    }
+
+    pub struct Neighbors {
+        dirs: &'static [(isize, isize)],
+        idx: usize,
+        pos: (usize, usize),
+        h: usize,
+        w: usize,
+    }
+
+    impl Iterator for Neighbors {
+        type Item = (usize, usize);
+        fn next(&mut self) -> Option<(usize, usize)> {
+            while self.idx < self.dirs.len() {
+                let (dr, dc) = self.dirs[self.idx];
+                self.idx += 1;
+                let r = self.pos.0.wrapping_add(dr as usize);
+                let c = self.pos.1.wrapping_add(dc as usize);
+                if r < self.h && c < self.w {
+                    return Some((r, c));
+                }
+            }
+            None
+        }
+    }
+
+    impl<T> Grid<T> {
+        pub fn new(cells: Vec<Vec<T>>) -> Self {
+            let h = cells.len();
+            let w = cells.first().map_or(0, |row| row.len());
+            assert!(cells.iter().all(|row| row.len() == w));
+            Self { h, w, cells }
+        }
+
+        pub fn height(&self) -> usize {
+            self.h
+        }
+
+        pub fn width(&self) -> usize {
+            self.w
+        }
+
+        pub fn id(&self, r: usize, c: usize) -> usize {
+            r * self.w + c
+        }
+
+        pub fn pos(&self, id: usize) -> (usize, usize) {
+            (id / self.w, id % self.w)
+        }
+
+        pub fn neighbors(&self, r: usize, c: usize, dirs: &'static [(isize, isize)]) -> Neighbors {
+            Neighbors { dirs, idx: 0, pos: (r, c), h: self.h, w: self.w }
+        }
+
+        pub fn neighbors4(&self, r: usize, c: usize) -> Neighbors {
+            self.neighbors(r, c, &DIR4)
+        }
+
+        pub fn neighbors8(&self, r: usize, c: usize) -> Neighbors {
+            self.neighbors(r, c, &DIR8)
+        }
+
+        pub fn rows(&self) -> &[Vec<T>] {
+            &self.cells
+        }
+
+        /// Shortest paths where entering a cell costs `cost(cell)`, which must be 0 or 1;
+        /// `None` means the cell cannot be entered.
+        pub fn zero_one_bfs<F>(&self, sources: &[(usize, usize)], cost: F) -> Vec<Vec<usize>>
+        where
+            F: Fn(&T) -> Option<usize>
+        {
+            let mut dists = vec![vec![UNREACHABLE; self.w]; self.h];
+            let mut dq = VecDeque::new();
+            for &(r, c) in sources {
+                dists[r][c] = 0;
+                dq.push_back((r, c));
+            }
+            while let Some((r, c)) = dq.pop_front() {
+                for (nr, nc) in self.neighbors4(r, c) {
+                    let w = match cost(&self.cells[nr][nc]) {
+                        Some(w) => w,
+                        None => continue,
+                    };
+                    debug_assert!(w <= 1);
+                    if dists[r][c] + w < dists[nr][nc] {
+                        dists[nr][nc] = dists[r][c] + w;
+                        if w == 0 {
+                            dq.push_front((nr, nc));
+                        } else {
+                            dq.push_back((nr, nc));
+                        }
+                    }
+                }
+            }
+            dists
+        }
+
+        /// Number of moves to each cell, moving only into cells with `passable(cell)`
+        pub fn bfs<F: Fn(&T) -> bool>(&self, sources: &[(usize, usize)], passable: F) -> Vec<Vec<usize>> {
+            self.zero_one_bfs(sources, |cell| if passable(cell) { Some(1) } else { None })
+        }
+
+        /// Shortest paths where entering a cell costs `cost(cell)` (`None` if impassable).
+        /// Unreachable cells have distance `C::max_value()`.
+        pub fn dijkstra<C, F>(&self, sources: &[(usize, usize)], cost: F) -> Vec<Vec<C>>
+        where
+            C: Monoid + Bounded + Ord,
+            F: Fn(&T) -> Option<C>,
+        {
+            let mut dists = vec![vec![C::max_value(); self.w]; self.h];
+            let mut pq = BinaryHeap::new();
+            for &(r, c) in sources {
+                dists[r][c] = C::zero();
+                pq.push(Reverse((C::zero(), r, c)));
+            }
+            while let Some(Reverse((d, r, c))) = pq.pop() {
+                if dists[r][c] < d { continue }
+                for (nr, nc) in self.neighbors4(r, c) {
+                    if let Some(w) = cost(&self.cells[nr][nc]) {
+                        if d + w < dists[nr][nc] {
+                            dists[nr][nc] = d + w;
+                            pq.push(Reverse((d + w, nr, nc)));
+                        }
+                    }
+                }
+            }
+            dists
+        }
+
+        /// Labels 4-connected components of cells with `same(a, b)` for adjacent `a`, `b`.
+        /// Cells with `valid(cell) == false` get `None`. Returns labels and the number of components.
+        pub fn components<V, S>(&self, valid: V, same: S) -> (Vec<Vec<Option<usize>>>, usize)
+        where
+            V: Fn(&T) -> bool,
+            S: Fn(&T, &T) -> bool,
+        {
+            let mut labels = vec![vec![None; self.w]; self.h];
+            let mut k = 0;
+            for r in 0..self.h {
+                for c in 0..self.w {
+                    if labels[r][c].is_some() || !valid(&self.cells[r][c]) { continue }
+                    labels[r][c] = Some(k);
+                    let mut stack = vec![(r, c)];
+                    while let Some((vr, vc)) = stack.pop() {
+                        for (nr, nc) in self.neighbors4(vr, vc) {
+                            let (a, b) = (&self.cells[vr][vc], &self.cells[nr][nc]);
+                            if labels[nr][nc].is_none() && valid(b) && same(a, b) {
+                                labels[nr][nc] = Some(k);
+                                stack.push((nr, nc));
+                            }
+                        }
+                    }
+                    k += 1;
+                }
+            }
+            (labels, k)
+        }
+    }
+
+    impl<T: Clone> Grid<T> {
+        pub fn transpose(&self) -> Self {
+            let cells = (0..self.w).map(|c| (0..self.h).map(|r| self.cells[r][c].clone()).collect()).collect();
+            Self { h: self.w, w: self.h, cells }
+        }
+
+        /// Rotates 90 degrees clockwise
+        pub fn rotate_cw(&self) -> Self {
+            let cells = (0..self.w).map(|c| (0..self.h).rev().map(|r| self.cells[r][c].clone()).collect()).collect();
+            Self { h: self.w, w: self.h, cells }
+        }
+
+        /// Rotates 90 degrees counterclockwise
+        pub fn rotate_ccw(&self) -> Self {
+            let cells = (0..self.w).rev().map(|c| (0..self.h).map(|r| self.cells[r][c].clone()).collect()).collect();
+            Self { h: self.w, w: self.h, cells }
+        }
+    }
+
+    impl<T> Index<(usize, usize)> for Grid<T> {
+        type Output = T;
+        fn index(&self, (r, c): (usize, usize)) -> &T {
+            &self.cells[r][c]
+        }
+    }
+
+    impl<T> IndexMut<(usize, usize)> for Grid<T> {
+        fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut T {
+            &mut self.cells[r][c]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::grid::*;
+
+    fn parse(s: &[&str]) -> Grid<char> {
+        Grid::new(s.iter().map(|row| row.chars().collect()).collect())
+    }
+
+    #[test]
+    fn test_grid_basic() {
+        let g = parse(&["ab.", "#c."]);
+        assert_eq!((g.height(), g.width()), (2, 3));
+        assert_eq!(g.id(1, 2), 5);
+        assert_eq!(g.pos(4), (1, 1));
+        assert_eq!(g[(1, 1)], 'c');
+        assert_eq!(g.neighbors4(0, 0).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(g.neighbors8(0, 1).count(), 5);
+        assert_eq!(g.neighbors8(1, 1).collect::<Vec<_>>(), vec![(0, 1), (0, 2), (1, 2), (1, 0), (0, 0)]);
+    }
+
+    #[test]
+    fn test_grid_rotate() {
+        let g = parse(&["abc", "def"]);
+        assert_eq!(g.transpose(), parse(&["ad", "be", "cf"]));
+        assert_eq!(g.rotate_cw(), parse(&["da", "eb", "fc"]));
+        assert_eq!(g.rotate_ccw(), parse(&["cf", "be", "ad"]));
+        assert_eq!(g.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), g);
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+    }
+
+    #[test]
+    fn test_grid_bfs() {
+        let g = parse(&[
+            "S..#",
+            "##.#",
+            "...G",
+            "#.##",
+        ]);
+        let d = g.bfs(&[(0, 0)], |&c| c != '#');
+        assert_eq!(d[2][3], 5);
+        assert_eq!(d[3][1], 6);
+        assert_eq!(d[0][3], UNREACHABLE);
+        assert_eq!(d[1][0], UNREACHABLE);
+    }
+
+    #[test]
+    fn test_grid_zero_one_bfs_and_dijkstra() {
+        let g = parse(&[
+            ".#..",
+            ".##.",
+            "....",
+        ]);
+        // walking on '.' is free, breaking a wall costs 1
+        let d = g.zero_one_bfs(&[(0, 0)], |&c| Some(if c == '#' { 1 } else { 0 }));
+        assert_eq!(d[0][3], 0);
+        assert_eq!(d[0][1], 1);
+        assert_eq!(d[1][2], 1);
+        let d = g.dijkstra(&[(0, 0)], |&c| if c == '#' { Some(10i64) } else { Some(1) });
+        assert_eq!(d[0][2], 8);
+        assert_eq!(d[0][1], 10);
+        let d = g.dijkstra(&[(0, 0)], |&c| if c == '#' { None } else { Some(1i64) });
+        assert_eq!(d[0][1], i64::MAX);
+    }
+
+    #[test]
+    fn test_grid_components() {
+        let g = parse(&[
+            "aab#",
+            "a#bb",
+            "#cc#",
+        ]);
+        let (labels, k) = g.components(|&c| c != '#', |a, b| a == b);
+        assert_eq!(k, 3);
+        assert_eq!(labels[0][0], labels[1][0]);
+        assert_eq!(labels[0][2], labels[1][3]);
+        assert_ne!(labels[0][1], labels[0][2]);
+        assert_eq!(labels[2][1], Some(2));
+        assert_eq!(labels[0][3], None);
+    }
+}
@@ -0,0 +1,312 @@
+use crate::graph::csr_graph::csr_graph;
+use crate::graph::strongly_connected_components::strongly_connected_components;
+
+pub mod hopcroft_karp {
+    use super::csr_graph::CsrGraph;
+    use super::strongly_connected_components::StronglyConnectedComponents;
+    use std::collections::VecDeque;
+
+    /// Maximum bipartite matching in O(E sqrt(V))
+    ///
+    /// Left vertices are `0..left` and right vertices are `0..right`.
+    #[derive(Debug, Clone)]
+    pub struct HopcroftKarp {
+        left: usize,
+        right: usize,
+        graph: Vec<Vec<usize>>,
+        edges: Vec<(usize, usize)>,
+        match_left: Vec<Option<usize>>,
+        match_right: Vec<Option<usize>>,
+        dist: Vec<usize>,
+        iter: Vec<usize>,
+    }
+
+    impl HopcroftKarp {
+        pub fn new(left: usize, right: usize) -> Self {
+            Self {
+                left,
+                right,
+                graph: vec![vec![]; left],
+                edges: vec![],
+                match_left: vec![None; left],
+                match_right: vec![None; right],
+                dist: vec![],
+                iter: vec![],
+            }
+        }
+
+        /// Adds an edge between left `u` and right `v` and returns its id
+        pub fn add_edge(&mut self, u: usize, v: usize) -> usize {
+            assert!(u < self.left && v < self.right);
+            self.graph[u].push(v);
+            self.edges.push((u, v));
+            self.edges.len() - 1
+        }
+
+        fn bfs(&mut self) -> bool {
+            self.dist = vec![usize::MAX; self.left];
+            let mut queue = VecDeque::new();
+            for u in 0..self.left {
+                if self.match_left[u].is_none() {
+                    self.dist[u] = 0;
+                    queue.push_back(u);
+                }
+            }
+            let mut found = false;
+            while let Some(u) = queue.pop_front() {
+                for &v in self.graph[u].iter() {
+                    match self.match_right[v] {
+                        Some(w) => {
+                            if self.dist[w] == usize::MAX {
+                                self.dist[w] = self.dist[u] + 1;
+                                queue.push_back(w);
+                            }
+                        }
+                        None => found = true,
+                    }
+                }
+            }
+            found
+        }
+
+        fn dfs(&mut self, u: usize) -> bool {
+            while self.iter[u] < self.graph[u].len() {
+                let v = self.graph[u][self.iter[u]];
+                self.iter[u] += 1;
+                let ok = match self.match_right[v] {
+                    None => true,
+                    Some(w) => self.dist[w] == self.dist[u] + 1 && self.dfs(w),
+                };
+                if ok {
+                    self.match_left[u] = Some(v);
+                    self.match_right[v] = Some(u);
+                    return true;
+                }
+            }
+            self.dist[u] = usize::MAX;
+            false
+        }
+
+        /// Size of a maximum matching
+        pub fn max_matching(&mut self) -> usize {
+            while self.bfs() {
+                self.iter = vec![0; self.left];
+                for u in 0..self.left {
+                    if self.match_left[u].is_none() {
+                        self.dfs(u);
+                    }
+                }
+            }
+            self.match_left.iter().filter(|m| m.is_some()).count()
+        }
+
+        /// Matched pairs `(left, right)` after `max_matching`
+        pub fn matching(&self) -> Vec<(usize, usize)> {
+            (0..self.left).filter_map(|u| self.match_left[u].map(|v| (u, v))).collect()
+        }
+
+        pub fn match_left(&self, u: usize) -> Option<usize> {
+            self.match_left[u]
+        }
+
+        pub fn match_right(&self, v: usize) -> Option<usize> {
+            self.match_right[v]
+        }
+
+        /// Vertices reachable from an unmatched left vertex by alternating paths
+        fn alternating_reach(&self) -> (Vec<bool>, Vec<bool>) {
+            let mut seen_left = vec![false; self.left];
+            let mut seen_right = vec![false; self.right];
+            let mut stack = (0..self.left).filter(|&u| self.match_left[u].is_none()).collect::<Vec<_>>();
+            for &u in stack.iter() {
+                seen_left[u] = true;
+            }
+            while let Some(u) = stack.pop() {
+                for &v in self.graph[u].iter() {
+                    if seen_right[v] { continue }
+                    seen_right[v] = true;
+                    if let Some(w) = self.match_right[v] {
+                        if !seen_left[w] {
+                            seen_left[w] = true;
+                            stack.push(w);
+                        }
+                    }
+                }
+            }
+            (seen_left, seen_right)
+        }
+
+        /// Minimum vertex cover `(left vertices, right vertices)` by König's theorem.
+        /// Call after `max_matching`.
+        pub fn min_vertex_cover(&self) -> (Vec<usize>, Vec<usize>) {
+            let (seen_left, seen_right) = self.alternating_reach();
+            let left = (0..self.left).filter(|&u| !seen_left[u]).collect();
+            let right = (0..self.right).filter(|&v| seen_right[v]).collect();
+            (left, right)
+        }
+
+        /// Maximum independent set `(left vertices, right vertices)`, the complement of
+        /// the minimum vertex cover. Call after `max_matching`.
+        pub fn max_independent_set(&self) -> (Vec<usize>, Vec<usize>) {
+            let (seen_left, seen_right) = self.alternating_reach();
+            let left = (0..self.left).filter(|&u| seen_left[u]).collect();
+            let right = (0..self.right).filter(|&v| !seen_right[v]).collect();
+            (left, right)
+        }
+
+        /// Whether each edge (by id) belongs to some maximum matching. Call after `max_matching`.
+        ///
+        /// Orient unmatched edges left to right and matched edges right to left. An unmatched
+        /// edge can be swapped in iff it lies on an alternating cycle (both ends in the same
+        /// SCC) or on an even alternating path from a free vertex.
+        pub fn allowed_edges(&self) -> Vec<bool> {
+            let (l, r) = (self.left, self.right);
+            let directed = self.edges.iter().map(|&(u, v)| {
+                if self.match_left[u] == Some(v) { (l + v, u, ()) } else { (u, l + v, ()) }
+            }).collect::<Vec<_>>();
+            let g = CsrGraph::new(l + r, &directed);
+            let mut scc = StronglyConnectedComponents::new(&g);
+            scc.build();
+
+            let (from_free_left, _) = self.alternating_reach();
+            // right vertices that reach an unmatched right vertex along the orientation
+            let rg = g.reverse();
+            let mut to_free_right = vec![false; l + r];
+            let mut stack = (0..r).filter(|&v| self.match_right[v].is_none()).map(|v| l + v).collect::<Vec<_>>();
+            for &v in stack.iter() {
+                to_free_right[v] = true;
+            }
+            while let Some(v) = stack.pop() {
+                for w in rg.neighbors(v) {
+                    if !to_free_right[w] {
+                        to_free_right[w] = true;
+                        stack.push(w);
+                    }
+                }
+            }
+
+            self.edges.iter().map(|&(u, v)| {
+                self.match_left[u] == Some(v)
+                    || scc.same(u, l + v)
+                    || from_free_left[u]
+                    || to_free_right[l + v]
+            }).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hopcroft_karp::*;
+    use crate::graph::dinic::dinic::Dinic;
+
+    #[test]
+    fn test_hopcroft_karp() {
+        let mut g = HopcroftKarp::new(3, 4);
+        g.add_edge(0, 0);
+        g.add_edge(0, 2);
+        g.add_edge(0, 3);
+        g.add_edge(1, 1);
+        g.add_edge(2, 1);
+        g.add_edge(2, 3);
+        assert_eq!(g.max_matching(), 3);
+        let m = g.matching();
+        assert_eq!(m.len(), 3);
+        for &(u, v) in m.iter() {
+            assert_eq!(g.match_left(u), Some(v));
+            assert_eq!(g.match_right(v), Some(u));
+        }
+    }
+
+    #[test]
+    fn test_konig() {
+        // a star on the right vertex 0 plus an isolated edge
+        let mut g = HopcroftKarp::new(4, 2);
+        g.add_edge(0, 0);
+        g.add_edge(1, 0);
+        g.add_edge(2, 0);
+        g.add_edge(3, 1);
+        assert_eq!(g.max_matching(), 2);
+        let (cl, cr) = g.min_vertex_cover();
+        assert_eq!(cl.len() + cr.len(), 2);
+        assert!(cr.contains(&0));
+        let (il, ir) = g.max_independent_set();
+        assert_eq!(il.len() + ir.len(), 4);
+        assert_eq!(il, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_allowed_edges() {
+        // every edge lies in some maximum matching
+        let mut g = HopcroftKarp::new(3, 3);
+        let e = [g.add_edge(0, 0), g.add_edge(0, 1), g.add_edge(1, 1), g.add_edge(1, 0), g.add_edge(2, 1)];
+        assert_eq!(g.max_matching(), 2);
+        let allowed = g.allowed_edges();
+        assert_eq!(e.iter().map(|&i| allowed[i]).collect::<Vec<_>>(), vec![true; 5]);
+
+        let mut g = HopcroftKarp::new(2, 2);
+        g.add_edge(0, 0);
+        g.add_edge(0, 1);
+        g.add_edge(1, 1);
+        assert_eq!(g.max_matching(), 2);
+        assert_eq!(g.allowed_edges(), vec![true, false, true]);
+    }
+
+    #[test]
+    fn test_hopcroft_karp_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..200 {
+            let (l, r) = (rng.gen_range(1..7), rng.gen_range(1..7));
+            let m = rng.gen_range(0..=std::cmp::min(l * r, 12));
+            let mut g = HopcroftKarp::new(l, r);
+            let mut edges = vec![];
+            for _ in 0..m {
+                let (u, v) = (rng.gen_range(0..l), rng.gen_range(0..r));
+                g.add_edge(u, v);
+                edges.push((u, v));
+            }
+            let size = g.max_matching();
+
+            let mut dinic = Dinic::new(l + r + 2);
+            for &(u, v) in edges.iter() {
+                dinic.add_flow(u, l + v, 1);
+            }
+            for u in 0..l {
+                dinic.add_flow(l + r, u, 1);
+            }
+            for v in 0..r {
+                dinic.add_flow(l + v, l + r + 1, 1);
+            }
+            assert_eq!(size, dinic.max_flow(l + r, l + r + 1) as usize);
+
+            let (cl, cr) = g.min_vertex_cover();
+            assert_eq!(cl.len() + cr.len(), size);
+            assert!(edges.iter().all(|(u, v)| cl.contains(u) || cr.contains(v)));
+            let (il, ir) = g.max_independent_set();
+            assert_eq!(il.len() + ir.len(), l + r - size);
+            assert!(edges.iter().all(|(u, v)| !(il.contains(u) && ir.contains(v))));
+
+            // brute force: does the edge belong to a matching of the maximum size?
+            let mut possible = vec![false; m];
+            for mask in 0u32..1 << m {
+                if mask.count_ones() as usize != size { continue }
+                let mut used_l = vec![false; l];
+                let mut used_r = vec![false; r];
+                let ok = (0..m).filter(|&i| mask >> i & 1 == 1).all(|i| {
+                    let (u, v) = edges[i];
+                    let fresh = !used_l[u] && !used_r[v];
+                    used_l[u] = true;
+                    used_r[v] = true;
+                    fresh
+                });
+                if ok {
+                    for (i, p) in possible.iter_mut().enumerate() {
+                        *p |= mask >> i & 1 == 1;
+                    }
+                }
+            }
+            assert_eq!(g.allowed_edges(), possible);
+        }
+    }
+}
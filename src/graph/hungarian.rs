@@ -0,0 +1,153 @@
+use crate::math::algebra::num_trait;
+
+pub mod hungarian {
+    use super::num_trait::*;
+
+    /// Result of `hungarian`
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Assignment<T> {
+        /// Minimum total cost
+        pub cost: T,
+        /// `assignment[i]` is the column assigned to row `i`
+        pub assignment: Vec<usize>,
+        /// Dual potentials with `row[i] + col[j] <= cost[i][j]`, tight on assigned pairs
+        pub row: Vec<T>,
+        pub col: Vec<T>,
+    }
+
+    /// Minimum cost assignment of every row to a distinct column in O(n^2 m)
+    ///
+    /// Requires `n <= m`; transpose the matrix otherwise.
+    pub fn hungarian<T: Ring + Bounded + Ord>(cost: &[Vec<T>]) -> Assignment<T> {
+        let n = cost.len();
+        let m = cost.first().map_or(0, |row| row.len());
+        assert!(n <= m);
+        // 1-indexed; column 0 is a sentinel holding the row being inserted
+        let mut u = vec![T::zero(); n + 1];
+        let mut v = vec![T::zero(); m + 1];
+        let mut p = vec![0; m + 1];
+        let mut way = vec![0; m + 1];
+        for i in 1..=n {
+            p[0] = i;
+            let mut j0 = 0;
+            let mut minv = vec![T::max_value(); m + 1];
+            let mut used = vec![false; m + 1];
+            loop {
+                used[j0] = true;
+                let i0 = p[j0];
+                let mut delta = T::max_value();
+                let mut j1 = 0;
+                for j in 1..=m {
+                    if used[j] { continue }
+                    let cur = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                    if cur < minv[j] {
+                        minv[j] = cur;
+                        way[j] = j0;
+                    }
+                    if minv[j] < delta {
+                        delta = minv[j];
+                        j1 = j;
+                    }
+                }
+                for j in 0..=m {
+                    if used[j] {
+                        u[p[j]] += delta;
+                        v[j] -= delta;
+                    } else {
+                        minv[j] -= delta;
+                    }
+                }
+                j0 = j1;
+                if p[j0] == 0 { break }
+            }
+            loop {
+                let j1 = way[j0];
+                p[j0] = p[j1];
+                j0 = j1;
+                if j0 == 0 { break }
+            }
+        }
+        let mut assignment = vec![0; n];
+        for j in 1..=m {
+            if p[j] != 0 {
+                assignment[p[j] - 1] = j - 1;
+            }
+        }
+        let mut total = T::zero();
+        for (i, &j) in assignment.iter().enumerate() {
+            total += cost[i][j];
+        }
+        Assignment { cost: total, assignment, row: u[1..].to_vec(), col: v[1..].to_vec() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hungarian::*;
+
+    fn check_duals(cost: &[Vec<i64>], res: &Assignment<i64>) {
+        for (i, row) in cost.iter().enumerate() {
+            for (j, &c) in row.iter().enumerate() {
+                assert!(res.row[i] + res.col[j] <= c);
+            }
+            let j = res.assignment[i];
+            assert_eq!(res.row[i] + res.col[j], cost[i][j]);
+        }
+    }
+
+    #[test]
+    fn test_hungarian() {
+        let cost = vec![
+            vec![4, 1, 3],
+            vec![2, 0, 5],
+            vec![3, 2, 2],
+        ];
+        let res = hungarian(&cost);
+        assert_eq!(res.cost, 5);
+        assert_eq!(res.assignment, vec![1, 0, 2]);
+        check_duals(&cost, &res);
+    }
+
+    #[test]
+    fn test_hungarian_rectangular() {
+        let cost = vec![
+            vec![7, 3, 9, 1],
+            vec![2, 8, 1, 6],
+        ];
+        let res = hungarian(&cost);
+        assert_eq!(res.cost, 2);
+        assert_eq!(res.assignment, vec![3, 2]);
+        check_duals(&cost, &res);
+    }
+
+    #[test]
+    fn test_hungarian_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(1..6);
+            let m = rng.gen_range(n..7);
+            let cost = (0..n).map(|_| (0..m).map(|_| rng.gen_range(-50..50i64)).collect()).collect::<Vec<Vec<_>>>();
+            let res = hungarian(&cost);
+            check_duals(&cost, &res);
+            let mut cols = res.assignment.clone();
+            cols.sort();
+            cols.dedup();
+            assert_eq!(cols.len(), n);
+
+            // brute force over injective assignments
+            fn rec(i: usize, used: &mut [bool], cost: &[Vec<i64>]) -> i64 {
+                if i == cost.len() { return 0 }
+                let mut best = i64::MAX;
+                for j in 0..used.len() {
+                    if used[j] { continue }
+                    used[j] = true;
+                    best = best.min(cost[i][j] + rec(i + 1, used, cost));
+                    used[j] = false;
+                }
+                best
+            }
+            assert_eq!(res.cost, rec(0, &mut vec![false; m], &cost));
+        }
+    }
+}
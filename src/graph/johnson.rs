@@ -0,0 +1,157 @@
+use crate::math::algebra::num_trait;
+use crate::graph::base::graph;
+use crate::graph::bellmanford::bellmanford;
+use crate::graph::dijkstra::dijkstra;
+
+pub mod johnson {
+    use super::num_trait::*;
+    use super::graph::*;
+    use super::bellmanford::BellmanFord;
+    use super::dijkstra::Dijkstra;
+
+    /// All pairs shortest paths in O(VE log V), allowing negative edges
+    #[derive(Debug, Clone)]
+    pub struct Johnson<'a, T> {
+        graph: &'a Graph<T>,
+        /// `dists[s][t]`; `T::max_value()` if unreachable
+        pub dists: Vec<Vec<T>>,
+        parents: Vec<Vec<Option<usize>>>,
+    }
+
+    impl<'a, T: AbelGroup + Bounded + Ord> Johnson<'a, T> {
+        pub fn new(graph: &'a Graph<T>) -> Self {
+            Self { graph, dists: vec![], parents: vec![] }
+        }
+
+        /// Returns the distance matrix, or `None` (leaving `dists` empty) if the graph has a negative cycle.
+        pub fn build_graph(&mut self) -> Option<&Vec<Vec<T>>> {
+            let n = self.graph.0.len();
+            self.dists.clear();
+            self.parents.clear();
+            let mut aux = Graph::new(n + 1);
+            for (v, edges) in self.graph.0.iter().enumerate() {
+                for edge in edges {
+                    aux.add_edge(v, edge.to, edge.cost);
+                }
+            }
+            for v in 0..n {
+                aux.add_edge(n, v, T::zero());
+            }
+            let mut bf = BellmanFord::new(&aux);
+            if !bf.build_graph_spfa(n) {
+                return None
+            }
+            let h = bf.dists;
+
+            let mut reweighted = Graph::new(n);
+            for (v, edges) in self.graph.0.iter().enumerate() {
+                for edge in edges {
+                    reweighted.add_edge(v, edge.to, edge.cost + h[v] - h[edge.to]);
+                }
+            }
+            self.dists = vec![vec![T::max_value(); n]; n];
+            self.parents = vec![vec![None; n]; n];
+            for s in 0..n {
+                let mut dijkstra = Dijkstra::new(&reweighted);
+                dijkstra.build_graph(s);
+                for t in 0..n {
+                    if dijkstra.dists[t] != T::max_value() {
+                        self.dists[s][t] = dijkstra.dists[t] + h[t] - h[s];
+                    }
+                    self.parents[s][t] = dijkstra.parent(t);
+                }
+            }
+            Some(&self.dists)
+        }
+
+        /// Vertices of a shortest path from `s` to `t`, both ends included
+        pub fn path(&self, s: usize, t: usize) -> Option<Vec<usize>> {
+            if self.dists[s][t] == T::max_value() { return None }
+            let mut path = vec![t];
+            let mut v = t;
+            while let Some(p) = self.parents[s][v] {
+                path.push(p);
+                v = p;
+            }
+            path.reverse();
+            Some(path)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::graph::*;
+    use super::johnson::*;
+    use crate::graph::warshall_floyd::warshall_floyd::WarshallFloyd;
+
+    #[test]
+    fn test_johnson() {
+        let mut g = Graph::new(4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(0, 2, 5);
+        g.add_edge(1, 2, 2);
+        g.add_edge(1, 3, 4);
+        g.add_edge(2, 3, 1);
+        g.add_edge(3, 2, 7);
+        let mut j = Johnson::new(&g);
+        let inf = i32::MAX;
+        assert_eq!(j.build_graph().map(|d| d[3].clone()), Some(vec![inf, inf, 7, 0]));
+        assert_eq!(j.dists[0], vec![  0,   1, 3, 4]);
+        assert_eq!(j.dists[1], vec![inf,   0, 2, 3]);
+        assert_eq!(j.dists[2], vec![inf, inf, 0, 1]);
+        assert_eq!(j.dists[3], vec![inf, inf, 7, 0]);
+        assert_eq!(j.path(0, 3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(j.path(2, 2), Some(vec![2]));
+        assert_eq!(j.path(3, 0), None);
+    }
+
+    #[test]
+    fn test_johnson_negative_cycle() {
+        let mut g = Graph::new(4);
+        g.add_edge(0, 1, 1);
+        g.add_edge(2, 3, 1);
+        let mut j = Johnson::new(&g);
+        assert!(j.build_graph().is_some());
+        g.add_edge(3, 2, -7);
+        let mut j = Johnson::new(&g);
+        assert_eq!(j.build_graph(), None);
+        assert!(j.dists.is_empty());
+    }
+
+    #[test]
+    fn test_johnson_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..30 {
+            let n = 15;
+            let mut g = Graph::new(n);
+            let mut wf = WarshallFloyd::new(n);
+            // edges only go forward, so negative costs never form a cycle
+            for _ in 0..40 {
+                let u = rng.gen_range(0..n - 1);
+                let v = rng.gen_range(u + 1..n);
+                let c = rng.gen_range(-10..30i64);
+                g.add_edge(u, v, c);
+                if wf[u][v] > c {
+                    wf.add_edge(u, v, c);
+                }
+            }
+            wf.build_graph();
+            let mut j = Johnson::new(&g);
+            assert!(j.build_graph().is_some());
+            for s in 0..n {
+                assert_eq!(j.dists[s], wf[s]);
+                for t in 0..n {
+                    if let Some(path) = j.path(s, t) {
+                        let mut d = 0;
+                        for w in path.windows(2) {
+                            d += g[w[0]].iter().filter(|e| e.to == w[1]).map(|e| e.cost).min().unwrap();
+                        }
+                        assert_eq!(d, j.dists[s][t]);
+                    }
+                }
+            }
+        }
+    }
+}
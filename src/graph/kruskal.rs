@@ -1,83 +1,245 @@
 //! Verified [AOJ GRL 2A](http://judge.u-aizu.ac.jp/onlinejudge/review.jsp?rid=5085970)
 use crate::math::algebra::num_trait;
+use crate::graph::base::graph;
 use crate::data_structure::unionfind::unionfind;
 
 pub mod kruskal {
     use super::num_trait::*;
+    use super::graph::*;
     use super::unionfind::UnionFind;
     use std::cmp::*;
 
-    pub struct Edge<T: AbelGroup + Eq + Ord> {
-        from: usize,
-        to: usize,
-        cost: T,
+    /// Minimum spanning forest of an undirected `Graph` or an edge list
+    ///
+    /// After `build`, the maximum edge on tree paths is available by doubling,
+    /// which answers second-best MST and "MST containing edge `e`" queries in O(log V).
+    #[derive(Debug, Clone)]
+    pub struct Kruskal<T> {
+        size: usize,
+        edges: Vec<Edge<T>>,
+        /// `position[id]`: index of the edge `id` in `edges`
+        position: Vec<usize>,
+        used: Vec<bool>,
+        weight: T,
+        depth: Vec<usize>,
+        /// `up[k][v]`: `2^k`-th ancestor and the maximum cost on the way
+        up: Vec<Vec<(usize, Option<T>)>>,
     }
 
-    impl<T: AbelGroup + Eq + Ord> PartialEq for Edge<T> {
-        fn eq(&self, other: &Self) -> bool {
-            self.cost.eq(&other.cost)
+    impl<T: AbelGroup + Ord> Kruskal<T> {
+        pub fn new(graph: &Graph<T>) -> Self {
+            let edges = graph.edge_list().into_iter().cloned().collect();
+            Self::with_edges(graph.0.len(), edges)
         }
-    }
-    impl<T: AbelGroup + Eq + Ord> PartialOrd for Edge<T> {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            self.cost.partial_cmp(&other.cost)
+
+        /// Undirected graph from `(u, v, cost)`; the `i`-th edge gets id `i`.
+        pub fn from_edges(size: usize, edges: &[(usize, usize, T)]) -> Self {
+            let edges = edges.iter().enumerate().map(|(i, &(u, v, c))| Edge::with_id(u, v, c, i)).collect();
+            Self::with_edges(size, edges)
         }
-    }
-    impl<T: AbelGroup + Eq + Ord> Eq for Edge<T> {}
-    impl<T: AbelGroup + Eq + Ord> Ord for Edge<T> {
-        fn cmp(&self, other: &Self) -> Ordering {
-            self.cost.cmp(&other.cost)
+
+        fn with_edges(size: usize, edges: Vec<Edge<T>>) -> Self {
+            let mut position = vec![usize::MAX; edges.iter().map(|e| e.id + 1).max().unwrap_or(0)];
+            for (i, edge) in edges.iter().enumerate() {
+                position[edge.id] = i;
+            }
+            let used = vec![false; edges.len()];
+            Self { size, edges, position, used, weight: T::zero(), depth: vec![], up: vec![] }
         }
-    }
 
-    pub struct Kruskal<T: AbelGroup + Eq + Ord> {
-        uft: UnionFind,
-        edges: Vec<Edge<T>>,
-    }
+        /// Returns the total weight of the minimum spanning forest.
+        pub fn build(&mut self) -> T {
+            let n = self.size;
+            self.weight = T::zero();
+            self.used = vec![false; self.edges.len()];
+            let mut order = (0..self.edges.len()).collect::<Vec<_>>();
+            order.sort_by_key(|&i| self.edges[i].cost);
+            let mut uft = UnionFind::new(n);
+            let mut tree = vec![vec![]; n];
+            for i in order {
+                let edge = &self.edges[i];
+                if uft.unite(edge.from, edge.to) {
+                    self.used[i] = true;
+                    self.weight += edge.cost;
+                    tree[edge.from].push((edge.to, edge.cost));
+                    tree[edge.to].push((edge.from, edge.cost));
+                }
+            }
 
-    impl<T: AbelGroup + Eq + Ord> Kruskal<T> {
-        pub fn new(size: usize) -> Self {
-            let uft = UnionFind::new(size);
-            let edges = vec![];
-            Self { uft, edges }
+            self.depth = vec![usize::MAX; n];
+            let mut first = vec![(0, None); n];
+            for root in 0..n {
+                if self.depth[root] != usize::MAX { continue }
+                self.depth[root] = 0;
+                first[root] = (root, None);
+                let mut stack = vec![root];
+                while let Some(v) = stack.pop() {
+                    for &(to, cost) in tree[v].iter() {
+                        if self.depth[to] != usize::MAX { continue }
+                        self.depth[to] = self.depth[v] + 1;
+                        first[to] = (v, Some(cost));
+                        stack.push(to);
+                    }
+                }
+            }
+            self.up = vec![first];
+            while 1 << self.up.len() < n {
+                let last = self.up.last().unwrap();
+                let next = last.iter().map(|&(p, c)| (last[p].0, max(c, last[p].1))).collect();
+                self.up.push(next);
+            }
+            self.weight
         }
 
-        pub fn add_edge_undirected(&mut self, from: usize, to: usize, cost: T) {
-            self.edges.push(Edge { from, to, cost });
-            self.edges.push(Edge { to, from, cost });
+        /// Edges of the minimum spanning forest
+        pub fn edges(&self) -> Vec<&Edge<T>> {
+            self.edges.iter().zip(self.used.iter()).filter(|(_, &used)| used).map(|(edge, _)| edge).collect()
         }
 
-        pub fn build(&mut self) -> T {
-            self.edges.sort();
-            let mut weight_sum = T::zero();
-            for edge in self.edges.iter() {
-                if !self.uft.is_same(edge.from, edge.to) {
-                    self.uft.unite(edge.from, edge.to);
-                    weight_sum += edge.cost;
+        pub fn is_used(&self, id: usize) -> bool {
+            self.used[self.position[id]]
+        }
+
+        /// Maximum cost on the tree path between `u` and `v` (`None` if `u == v`).
+        /// `u` and `v` must be in the same tree.
+        pub fn path_max(&self, mut u: usize, mut v: usize) -> Option<T> {
+            let mut ret = None;
+            if self.depth[u] < self.depth[v] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            let diff = self.depth[u] - self.depth[v];
+            for (k, up) in self.up.iter().enumerate() {
+                if diff >> k & 1 == 1 {
+                    ret = max(ret, up[u].1);
+                    u = up[u].0;
+                }
+            }
+            if u == v { return ret }
+            for up in self.up.iter().rev() {
+                if up[u].0 != up[v].0 {
+                    ret = max(ret, max(up[u].1, up[v].1));
+                    u = up[u].0;
+                    v = up[v].0;
                 }
             }
-            weight_sum
+            max(ret, max(self.up[0][u].1, self.up[0][v].1))
+        }
+
+        /// Weight of the minimum spanning forest that contains the edge `id`
+        /// (`None` for a self loop)
+        pub fn with_edge(&self, id: usize) -> Option<T> {
+            let i = self.position[id];
+            if self.used[i] { return Some(self.weight) }
+            let edge = &self.edges[i];
+            let removed = self.path_max(edge.from, edge.to)?;
+            Some(self.weight + edge.cost - removed)
+        }
+
+        /// Minimum weight of a spanning forest different from the one found by `build`,
+        /// or `None` if there is no other
+        pub fn second_best(&self) -> Option<T> {
+            self.edges.iter()
+                .zip(self.used.iter())
+                .filter(|(_, &used)| !used)
+                .filter_map(|(edge, _)| self.with_edge(edge.id))
+                .min()
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::graph::*;
     use super::kruskal::*;
 
     #[test]
     fn test_kruskal() {
-        let mut kr = Kruskal::new(6);
-        kr.add_edge_undirected(0, 1, 1);
-        kr.add_edge_undirected(0, 2, 3);
-        kr.add_edge_undirected(1, 2, 1);
-        kr.add_edge_undirected(1, 3, 7);
-        kr.add_edge_undirected(2, 4, 1);
-        kr.add_edge_undirected(1, 4, 3);
-        kr.add_edge_undirected(3, 4, 1);
-        kr.add_edge_undirected(3, 5, 1);
-        kr.add_edge_undirected(4, 5, 6);
+        let mut g = Graph::new(6);
+        g.add_edge_undirected(0, 1, 1);
+        g.add_edge_undirected(0, 2, 3);
+        g.add_edge_undirected(1, 2, 1);
+        g.add_edge_undirected(1, 3, 7);
+        g.add_edge_undirected(2, 4, 1);
+        g.add_edge_undirected(1, 4, 3);
+        g.add_edge_undirected(3, 4, 1);
+        g.add_edge_undirected(3, 5, 1);
+        g.add_edge_undirected(4, 5, 6);
+        let mut kr = Kruskal::new(&g);
         let w = kr.build();
         assert_eq!(w, 5);
+        let ids = kr.edges().iter().map(|e| e.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![0, 2, 4, 6, 7]);
+        assert_eq!(kr.with_edge(3), Some(11));
+        assert_eq!(kr.with_edge(1), Some(7));
+        assert_eq!(kr.second_best(), Some(7));
+        assert_eq!(kr.build(), 5);
+        assert_eq!(kr.edges().len(), 5);
+    }
+
+    #[test]
+    fn test_kruskal_from_edges() {
+        let edges = [(0, 1, 1), (0, 2, 3), (1, 2, 1), (1, 3, 7), (2, 4, 1), (1, 4, 3), (3, 4, 1), (3, 5, 1), (4, 5, 6)];
+        let mut kr = Kruskal::from_edges(6, &edges);
+        assert_eq!(kr.build(), 5);
+        let ids = kr.edges().iter().map(|e| e.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![0, 2, 4, 6, 7]);
+        assert_eq!(kr.with_edge(3), Some(11));
+    }
+
+    #[test]
+    fn test_kruskal_sparse_ids() {
+        use super::graph::Edge;
+        // ids 3, 7, 9 and 10 given by hand
+        let g = Graph::from(vec![
+            vec![Edge::with_id(0, 1, 4, 7), Edge::with_id(0, 2, 1, 3)],
+            vec![Edge::with_id(1, 2, 2, 9)],
+            vec![Edge::with_id(2, 0, 5, 10)],
+        ]);
+        let mut kr = Kruskal::new(&g);
+        assert_eq!(kr.build(), 3);
+        assert!(kr.is_used(3) && kr.is_used(9) && !kr.is_used(7) && !kr.is_used(10));
+        assert_eq!(kr.with_edge(7), Some(5));
+        assert_eq!(kr.with_edge(10), Some(7));
+        assert_eq!(kr.second_best(), Some(5));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_kruskal_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(1..7);
+            let m = rng.gen_range(0..10);
+            let mut g = Graph::new(n);
+            for _ in 0..m {
+                g.add_edge_undirected(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..10i64));
+            }
+            let mut kr = Kruskal::new(&g);
+            let w = kr.build();
+
+            // brute force over all edge subsets that form a spanning forest of maximum size
+            let edges = g.edge_list();
+            let mut weights = vec![];
+            let mut best_with = vec![None; m];
+            for mask in 0u32..1 << m {
+                let mut uf = crate::data_structure::unionfind::unionfind::UnionFind::new(n);
+                let ok = (0..m).filter(|&i| mask >> i & 1 == 1).all(|i| uf.unite(edges[i].from, edges[i].to));
+                if !ok || mask.count_ones() as usize != kr.edges().len() { continue }
+                let cost = (0..m).filter(|&i| mask >> i & 1 == 1).map(|i| edges[i].cost).sum::<i64>();
+                weights.push(cost);
+                for (i, best) in best_with.iter_mut().enumerate() {
+                    if mask >> i & 1 == 1 && best.map_or(true, |b| cost < b) {
+                        *best = Some(cost);
+                    }
+                }
+            }
+            weights.sort();
+            assert_eq!(w, weights[0]);
+            assert_eq!(kr.edges().iter().map(|e| e.cost).sum::<i64>(), w);
+            assert_eq!(kr.second_best(), weights.get(1).copied());
+            for (id, &best) in best_with.iter().enumerate() {
+                assert_eq!(kr.with_edge(id), best);
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::math::algebra::num_trait;
+use crate::graph::dinic::dinic;
+
+pub mod lower_bound_flow {
+    use super::num_trait::*;
+    use super::dinic::Dinic;
+
+    /// Flow where each edge must carry between `lo` and `hi` units
+    ///
+    /// Every query builds a fresh `Dinic` with a super source and a super sink that
+    /// supply the lower bounds, so queries can be repeated. After a successful query
+    /// the flow on each edge is available through `flow`.
+    #[derive(Debug, Clone)]
+    pub struct LowerBoundFlow<T> {
+        size: usize,
+        edges: Vec<(usize, usize, T, T)>,
+        flows: Vec<T>,
+    }
+
+    impl<T: Ring + Bounded + Eq + Ord> LowerBoundFlow<T> {
+        pub fn new(size: usize) -> Self {
+            Self { size, edges: vec![], flows: vec![] }
+        }
+
+        /// Adds an edge whose flow must be in `[lo, hi]` and returns its id
+        pub fn add_edge(&mut self, from: usize, to: usize, lo: T, hi: T) -> usize {
+            assert!(lo <= hi);
+            self.edges.push((from, to, lo, hi));
+            self.edges.len() - 1
+        }
+
+        /// Flow on the edge `id` found by the last successful query
+        pub fn flow(&self, id: usize) -> T {
+            self.flows[id]
+        }
+
+        /// Reduced network: (dinic, super source, super sink, total demand)
+        fn build(&self) -> (Dinic<T>, usize, usize, T) {
+            let (ss, tt) = (self.size, self.size + 1);
+            let mut dinic = Dinic::new(self.size + 2);
+            let mut excess = vec![T::zero(); self.size];
+            for &(from, to, lo, hi) in self.edges.iter() {
+                dinic.add_flow(from, to, hi - lo);
+                excess[to] += lo;
+                excess[from] -= lo;
+            }
+            let mut demand = T::zero();
+            for (v, &e) in excess.iter().enumerate() {
+                if e > T::zero() {
+                    dinic.add_flow(ss, v, e);
+                    demand += e;
+                } else if e < T::zero() {
+                    dinic.add_flow(v, tt, -e);
+                }
+            }
+            (dinic, ss, tt, demand)
+        }
+
+        fn record(&mut self, dinic: &Dinic<T>) {
+            self.flows = self.edges.iter().enumerate().map(|(id, e)| e.2 + dinic.flow(id)).collect();
+        }
+
+        /// Whether a circulation satisfying all bounds exists
+        pub fn circulation(&mut self) -> bool {
+            let (mut dinic, ss, tt, demand) = self.build();
+            if dinic.max_flow(ss, tt) != demand { return false }
+            self.record(&dinic);
+            true
+        }
+
+        /// Maximum `s`-`t` flow satisfying all bounds, or `None` if infeasible
+        pub fn max_flow(&mut self, s: usize, t: usize) -> Option<T> {
+            let (mut dinic, ss, tt, demand) = self.build();
+            dinic.add_flow(t, s, T::max_value());
+            if dinic.max_flow(ss, tt) != demand { return None }
+            // the super source and sink are saturated, so no augmenting path passes them
+            dinic.max_flow(s, t);
+            self.record(&dinic);
+            Some(self.value(s))
+        }
+
+        /// Minimum `s`-`t` flow satisfying all bounds, or `None` if infeasible
+        pub fn min_flow(&mut self, s: usize, t: usize) -> Option<T> {
+            let (mut dinic, ss, tt, demand) = self.build();
+            let f = dinic.max_flow(ss, tt);
+            let back = dinic.add_flow(t, s, T::max_value());
+            if f + dinic.max_flow(ss, tt) != demand { return None }
+            self.record(&dinic);
+            Some(dinic.flow(back))
+        }
+
+        /// Net flow out of `s` in the last result
+        fn value(&self, s: usize) -> T {
+            let mut ret = T::zero();
+            for (&(from, to, _, _), &f) in self.edges.iter().zip(self.flows.iter()) {
+                if from == s { ret += f }
+                if to == s { ret -= f }
+            }
+            ret
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lower_bound_flow::*;
+    use super::dinic::Dinic;
+
+    fn check_flows(g: &LowerBoundFlow<i64>, n: usize, edges: &[(usize, usize, i64, i64)], s: usize, t: usize) -> i64 {
+        let mut balance = vec![0; n];
+        for (id, &(u, v, lo, hi)) in edges.iter().enumerate() {
+            let f = g.flow(id);
+            assert!(lo <= f && f <= hi);
+            balance[u] -= f;
+            balance[v] += f;
+        }
+        for (v, &b) in balance.iter().enumerate() {
+            if v != s && v != t {
+                assert_eq!(b, 0);
+            }
+        }
+        -balance[s]
+    }
+
+    #[test]
+    fn test_lower_bound_flow() {
+        let edges = [(0, 1, 1, 3), (0, 2, 0, 2), (1, 3, 2, 2), (2, 3, 0, 3), (1, 2, 0, 1)];
+        let mut g = LowerBoundFlow::new(4);
+        for &(u, v, lo, hi) in edges.iter() {
+            g.add_edge(u, v, lo, hi);
+        }
+        assert_eq!(g.max_flow(0, 3), Some(5));
+        assert_eq!(check_flows(&g, 4, &edges, 0, 3), 5);
+        assert_eq!(g.min_flow(0, 3), Some(2));
+        assert_eq!(check_flows(&g, 4, &edges, 0, 3), 2);
+        assert_eq!((g.flow(0), g.flow(2), g.flow(4)), (2, 2, 0));
+        assert!(!g.circulation());
+    }
+
+    #[test]
+    fn test_lower_bound_flow_infeasible() {
+        let mut g = LowerBoundFlow::new(3);
+        g.add_edge(0, 1, 3, 5);
+        g.add_edge(1, 2, 0, 2);
+        assert_eq!(g.max_flow(0, 2), None);
+        assert_eq!(g.min_flow(0, 2), None);
+    }
+
+    #[test]
+    fn test_circulation() {
+        let mut g = LowerBoundFlow::new(3);
+        g.add_edge(0, 1, 1, 2);
+        g.add_edge(1, 2, 2, 3);
+        let e = g.add_edge(2, 0, 0, 5);
+        assert!(g.circulation());
+        assert!(g.flow(e) >= 2);
+        let mut g = LowerBoundFlow::new(3);
+        g.add_edge(0, 1, 1, 2);
+        g.add_edge(1, 2, 2, 3);
+        g.add_edge(2, 0, 0, 1);
+        assert!(!g.circulation());
+    }
+
+    #[test]
+    fn test_lower_bound_flow_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = 8;
+            let (s, t) = (0, n - 1);
+            let mut edges = vec![];
+            for _ in 0..20 {
+                let u = rng.gen_range(0..n);
+                let v = rng.gen_range(0..n);
+                if u == v { continue }
+                let hi = rng.gen_range(0..6);
+                let lo = if rng.gen_bool(0.3) { rng.gen_range(0..=hi) } else { 0 };
+                edges.push((u, v, lo, hi));
+            }
+            let mut g = LowerBoundFlow::new(n);
+            let mut dinic = Dinic::new(n);
+            for &(u, v, lo, hi) in edges.iter() {
+                g.add_edge(u, v, lo, hi);
+                dinic.add_flow(u, v, hi);
+            }
+            let upper = dinic.max_flow(s, t);
+            match g.max_flow(s, t) {
+                Some(f) => {
+                    assert_eq!(check_flows(&g, n, &edges, s, t), f);
+                    assert!(f <= upper);
+                    let m = g.min_flow(s, t).unwrap();
+                    assert_eq!(check_flows(&g, n, &edges, s, t), m);
+                    assert!(m <= f);
+                    // a circulation through an extra edge t -> s is feasible exactly for values in [m, f]
+                    for (lo, hi, ok) in [(f, f, true), (f + 1, f + 100, false), (m, m, true), (0, m - 1, false)] {
+                        if hi < lo { continue }
+                        let mut h = g.clone();
+                        h.add_edge(t, s, lo, hi);
+                        assert_eq!(h.circulation(), ok);
+                    }
+                }
+                None => assert_eq!(g.min_flow(s, t), None),
+            }
+            if edges.iter().all(|e| e.2 == 0) {
+                assert_eq!(g.max_flow(s, t), Some(upper));
+                assert_eq!(g.min_flow(s, t), Some(0));
+            }
+        }
+    }
+}
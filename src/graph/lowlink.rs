@@ -5,48 +5,86 @@ use crate::graph::base::graph;
 pub mod lowlink {
     use super::graph::*;
     use std::cmp::*;
+    use std::marker::PhantomData;
 
+    /// Articulation points, bridges and biconnected components of an undirected graph
+    ///
+    /// Every edge must be present in both directions, either sharing one id or with an id each;
+    /// parallel edges are allowed.
     #[derive(Debug)]
-    pub struct LowLink<'a, T> {
-        graph: &'a Graph<T>,
+    pub struct LowLink<'a, T, G = Graph<T>> {
+        graph: &'a G,
         used: Vec<bool>,
         ord: Vec<usize>,
         low: Vec<usize>,
+        is_bridge: Vec<bool>,
+        /// id of the edge back to the DFS parent, skipped during the search
+        parent_edge: Vec<Option<usize>>,
+        stack: Vec<&'a Edge<T>>,
         pub articulations: Vec<usize>,
         pub bridges: Vec<(usize, usize)>,
+        /// Edge partition into biconnected components, one direction per edge (self loops belong to none)
+        pub biconnected_components: Vec<Vec<&'a Edge<T>>>,
+        _marker: PhantomData<T>,
     }
 
-    impl<'a, T: std::fmt::Debug> LowLink<'a, T> {
-        pub fn new(graph: &'a Graph<T>) -> Self {
-            let used = vec![false; graph.0.len()];
-            let ord = vec![0; graph.0.len()];
-            let low = vec![0; graph.0.len()];
+    impl<'a, T, G: AdjacencyList<Cost = T>> LowLink<'a, T, G> {
+        pub fn new(graph: &'a G) -> Self {
+            let n = graph.vertex_count();
+            let m = (0..n).flat_map(|v| graph.edges(v).iter()).map(|e| e.id + 1).max().unwrap_or(0);
+            let used = vec![false; n];
+            let ord = vec![0; n];
+            let low = vec![0; n];
+            let is_bridge = vec![false; m];
             let articulations = vec![];
             let bridges = vec![];
-            Self { graph, used, ord, low, articulations, bridges }
+            Self {
+                graph, used, ord, low, is_bridge, parent_edge: vec![None; n], stack: vec![],
+                articulations, bridges, biconnected_components: vec![], _marker: PhantomData,
+            }
         }
 
-        fn dfs(&mut self, idx: usize, mut k: usize, par: isize) -> usize {
+        fn dfs(&mut self, idx: usize, mut k: usize, par: Option<usize>) -> usize {
+            let graph = self.graph;
             self.used[idx] = true;
             self.ord[idx] = k;
             k += 1;
             self.low[idx] = self.ord[idx];
             let mut is_articulation = false;
             let mut ctr = 0;
-            for edge in self.graph[idx].iter() {
+            for edge in graph.edges(idx).iter() {
+                // skip exactly one edge back to the parent so that parallel edges form a cycle
+                if par == Some(edge.to) && self.parent_edge[idx].is_none() {
+                    self.parent_edge[idx] = Some(edge.id);
+                    continue;
+                }
                 if !self.used[edge.to] {
                     ctr += 1;
-                    k = self.dfs(edge.to, k, idx as isize);
+                    self.stack.push(edge);
+                    k = self.dfs(edge.to, k, Some(idx));
                     self.low[idx] = min(self.low[idx], self.low[edge.to]);
-                    is_articulation |= par != -1 && self.low[edge.to] >= self.ord[idx];
+                    if self.low[edge.to] >= self.ord[idx] {
+                        is_articulation |= par.is_some();
+                        let mut component = vec![];
+                        while let Some(e) = self.stack.pop() {
+                            component.push(e);
+                            if std::ptr::eq(e, edge) { break }
+                        }
+                        self.biconnected_components.push(component);
+                    }
                     if self.ord[idx] < self.low[edge.to] {
+                        self.is_bridge[edge.id] = true;
+                        if let Some(id) = self.parent_edge[edge.to] {
+                            self.is_bridge[id] = true;
+                        }
                         self.bridges.push((min(idx, edge.to), max(idx, edge.to)));
                     }
-                } else if edge.to != (par as usize) {
+                } else if self.ord[edge.to] < self.ord[idx] {
+                    self.stack.push(edge);
                     self.low[idx] = min(self.low[idx], self.ord[edge.to]);
                 }
             }
-            is_articulation |= par == -1 && ctr > 1;
+            is_articulation |= par.is_none() && ctr > 1;
             if is_articulation {
                 self.articulations.push(idx);
             }
@@ -55,14 +93,78 @@ pub mod lowlink {
 
         pub fn build(&mut self) {
             let mut k = 0;
-            for i in 0..self.graph.0.len() {
+            for i in 0..self.graph.vertex_count() {
                 if !self.used[i] {
-                    k = self.dfs(i, k, -1);
+                    k = self.dfs(i, k, None);
                 }
             }
             self.articulations.sort_unstable();
             self.bridges.sort_unstable();
         }
+
+        /// Whether the edge `id` is a bridge
+        pub fn is_bridge(&self, id: usize) -> bool {
+            self.is_bridge[id]
+        }
+
+        /// Labels of two-edge-connected components and their number.
+        pub fn two_edge_connected_components(&self) -> (Vec<usize>, usize) {
+            let n = self.graph.vertex_count();
+            let mut comp = vec![usize::MAX; n];
+            let mut k = 0;
+            for root in 0..n {
+                if comp[root] != usize::MAX { continue }
+                comp[root] = k;
+                let mut stack = vec![root];
+                while let Some(v) = stack.pop() {
+                    for edge in self.graph.edges(v).iter() {
+                        if self.is_bridge[edge.id] || comp[edge.to] != usize::MAX { continue }
+                        comp[edge.to] = k;
+                        stack.push(edge.to);
+                    }
+                }
+                k += 1;
+            }
+            (comp, k)
+        }
+
+        /// Block-cut tree (forest): vertex `v` stays node `v` and the `i`-th biconnected
+        /// component becomes node `n + i`, adjacent to every vertex it contains.
+        ///
+        /// Articulation points are exactly the vertices of degree at least 2.
+        pub fn block_cut_tree(&self) -> Graph<()> {
+            let n = self.graph.vertex_count();
+            let mut tree = Graph::new(n + self.biconnected_components.len());
+            let mut last = vec![usize::MAX; n];
+            for (i, component) in self.biconnected_components.iter().enumerate() {
+                for edge in component.iter() {
+                    for v in [edge.from, edge.to] {
+                        if last[v] != i {
+                            last[v] = i;
+                            tree.add_edge_undirected(v, n + i, ());
+                        }
+                    }
+                }
+            }
+            tree
+        }
+    }
+
+    impl<'a, T: Clone, G: AdjacencyList<Cost = T>> LowLink<'a, T, G> {
+        /// Tree (forest) of two-edge-connected components joined by the bridges,
+        /// with nodes labeled as in `two_edge_connected_components`
+        pub fn bridge_tree(&self) -> Graph<T> {
+            let (comp, k) = self.two_edge_connected_components();
+            let mut tree = Graph::new(k);
+            for v in 0..self.graph.vertex_count() {
+                for edge in self.graph.edges(v).iter() {
+                    if self.is_bridge[edge.id] && v < edge.to {
+                        tree.add_edge_undirected(comp[v], comp[edge.to], edge.cost.clone());
+                    }
+                }
+            }
+            tree
+        }
     }
 }
 
@@ -105,4 +207,197 @@ mod tests {
         g.build();
         assert_eq!(g.bridges, vec![(0, 3), (1, 5), (3, 4)]);
     }
+
+    #[test]
+    fn test_lowlink_csr() {
+        use crate::graph::csr_graph::csr_graph::CsrGraph;
+        let edges = [(0, 1, 0), (0, 2, 0), (0, 3, 0), (1, 2, 0), (1, 5, 0), (3, 4, 0)];
+        let g = CsrGraph::new_undirected(6, &edges);
+        let mut g = LowLink::new(&g);
+        g.build();
+        assert_eq!(g.articulations, vec![0, 1, 3]);
+        assert_eq!(g.bridges, vec![(0, 3), (1, 5), (3, 4)]);
+    }
+
+    #[test]
+    fn test_two_edge_connected_components() {
+        // 0 = 1 is a double edge, so only 1 - 2 and 3 - 4 are bridges
+        let mut g = Graph::new(5);
+        g.add_edge_undirected(0, 1, 1);
+        g.add_edge_undirected(1, 0, 2);
+        g.add_edge_undirected(1, 2, 3);
+        g.add_edge_undirected(2, 3, 4);
+        g.add_edge_undirected(3, 2, 5);
+        g.add_edge_undirected(3, 4, 6);
+        let mut ll = LowLink::new(&g);
+        ll.build();
+        assert_eq!(ll.bridges, vec![(1, 2), (3, 4)]);
+        assert!(ll.is_bridge(2) && !ll.is_bridge(0));
+        let (comp, k) = ll.two_edge_connected_components();
+        assert_eq!(comp, vec![0, 0, 1, 1, 2]);
+        assert_eq!(k, 3);
+        let tree = ll.bridge_tree();
+        let edges = tree.edge_list().iter().map(|e| (e.from, e.to, e.cost)).collect::<Vec<_>>();
+        assert_eq!(edges, vec![(0, 1, 3), (1, 2, 6)]);
+    }
+
+    #[test]
+    fn test_lowlink_directed_pairs() {
+        // each direction added separately gets its own id
+        let mut g = Graph::new(4);
+        for &(u, v) in [(0, 1), (1, 2), (2, 3), (3, 1)].iter() {
+            g.add_edge(u, v, 0);
+            g.add_edge(v, u, 0);
+        }
+        let mut ll = LowLink::new(&g);
+        ll.build();
+        assert_eq!(ll.bridges, vec![(0, 1)]);
+        assert_eq!(ll.articulations, vec![1]);
+        assert!(ll.is_bridge(0) && ll.is_bridge(1) && !ll.is_bridge(2));
+        assert_eq!(ll.two_edge_connected_components(), (vec![0, 1, 1, 1], 2));
+        assert_eq!(ll.bridge_tree().edge_count(), 1);
+        assert_eq!(ll.biconnected_components.len(), 2);
+        assert_eq!(ll.biconnected_components.iter().map(|c| c.len()).sum::<usize>(), 4);
+        assert_eq!(ll.block_cut_tree().edge_count(), 5);
+
+        let mut g = Graph::new(3);
+        for &(u, v) in [(0, 1), (1, 2)].iter() {
+            g.add_edge(u, v, 0);
+            g.add_edge(v, u, 0);
+        }
+        let mut ll = LowLink::new(&g);
+        ll.build();
+        assert_eq!(ll.bridges, vec![(0, 1), (1, 2)]);
+        assert_eq!(ll.articulations, vec![1]);
+    }
+
+    #[test]
+    fn test_block_cut_tree() {
+        let mut g = Graph::new(6);
+        g.add_edge_undirected(0, 1, 0);
+        g.add_edge_undirected(0, 2, 0);
+        g.add_edge_undirected(0, 3, 0);
+        g.add_edge_undirected(1, 2, 0);
+        g.add_edge_undirected(1, 5, 0);
+        g.add_edge_undirected(3, 4, 0);
+        let mut ll = LowLink::new(&g);
+        ll.build();
+        let mut blocks = ll.biconnected_components.iter().map(|c| {
+            let mut ids = c.iter().map(|e| e.id).collect::<Vec<_>>();
+            ids.sort();
+            ids
+        }).collect::<Vec<_>>();
+        blocks.sort();
+        assert_eq!(blocks, vec![vec![0, 1, 3], vec![2], vec![4], vec![5]]);
+        let tree = ll.block_cut_tree();
+        assert_eq!(tree.0.len(), 10);
+        assert_eq!(tree.edge_count(), 9);
+        let cut = (0..6).filter(|&v| tree[v].len() >= 2).collect::<Vec<_>>();
+        assert_eq!(cut, ll.articulations);
+    }
+
+    #[test]
+    fn test_lowlink_random() {
+        use crate::data_structure::unionfind::unionfind::UnionFind;
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..200 {
+            let n = rng.gen_range(1..8);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..12) {
+                g.add_edge_undirected(rng.gen_range(0..n), rng.gen_range(0..n), 0);
+            }
+            let edges = g.edge_list();
+            let m = edges.len();
+            let mut ll = LowLink::new(&g);
+            ll.build();
+
+            // connected pairs when skipping the edge `skip` and the vertex `removed`
+            let components = |skip: Option<usize>, removed: Option<usize>| {
+                let mut uf = UnionFind::new(n);
+                for e in edges.iter() {
+                    if Some(e.id) != skip && Some(e.from) != removed && Some(e.to) != removed {
+                        uf.unite(e.from, e.to);
+                    }
+                }
+                (0..n).filter(|&v| Some(v) != removed && uf.find(v) == v).count()
+            };
+            let base = components(None, None);
+            for e in edges.iter() {
+                assert_eq!(ll.is_bridge(e.id), components(Some(e.id), None) > base);
+            }
+            let cut = (0..n).filter(|&v| {
+                let isolated = edges.iter().all(|e| e.from != v && e.to != v || e.from == e.to);
+                !isolated && components(None, Some(v)) > base
+            }).collect::<Vec<_>>();
+            assert_eq!(ll.articulations, cut);
+
+            let (comp, _) = ll.two_edge_connected_components();
+            let mut uf = UnionFind::new(n);
+            for e in edges.iter().filter(|e| !ll.is_bridge(e.id)) {
+                uf.unite(e.from, e.to);
+            }
+            for u in 0..n {
+                for v in 0..n {
+                    assert_eq!(comp[u] == comp[v], uf.is_same(u, v));
+                }
+            }
+
+            // edges at a common vertex x share a block iff their other ends meet without x
+            let mut block = UnionFind::new(m);
+            for x in 0..n {
+                let mut uf = UnionFind::new(n);
+                for e in edges.iter().filter(|e| e.from != x && e.to != x) {
+                    uf.unite(e.from, e.to);
+                }
+                let inc = edges.iter().filter(|e| e.from != e.to && (e.from == x || e.to == x)).collect::<Vec<_>>();
+                for a in inc.iter() {
+                    for b in inc.iter() {
+                        let (p, q) = (a.from + a.to - x, b.from + b.to - x);
+                        if p == q || uf.is_same(p, q) {
+                            block.unite(a.id, b.id);
+                        }
+                    }
+                }
+            }
+            let mut label = vec![usize::MAX; m];
+            for (i, component) in ll.biconnected_components.iter().enumerate() {
+                for e in component.iter() {
+                    assert_eq!(label[e.id], usize::MAX);
+                    label[e.id] = i;
+                }
+            }
+            for a in edges.iter() {
+                assert_eq!(label[a.id] == usize::MAX, a.from == a.to);
+                for b in edges.iter() {
+                    if a.from == a.to || b.from == b.to { continue }
+                    assert_eq!(label[a.id] == label[b.id], block.is_same(a.id, b.id));
+                }
+            }
+
+            // the same graph with an id for each direction
+            let mut h = Graph::new(n);
+            for e in edges.iter() {
+                h.add_edge(e.from, e.to, 0);
+                if e.from != e.to {
+                    h.add_edge(e.to, e.from, 0);
+                }
+            }
+            let mut lh = LowLink::new(&h);
+            lh.build();
+            assert_eq!(lh.bridges, ll.bridges);
+            assert_eq!(lh.articulations, ll.articulations);
+            assert_eq!(lh.two_edge_connected_components(), ll.two_edge_connected_components());
+            assert_eq!(lh.biconnected_components.len(), ll.biconnected_components.len());
+            assert_eq!(lh.block_cut_tree().edge_count(), ll.block_cut_tree().edge_count());
+
+            let tree = ll.block_cut_tree();
+            let mut uf = UnionFind::new(tree.0.len());
+            for e in tree.edge_list() {
+                assert!(uf.unite(e.from, e.to));
+            }
+            let cut = (0..n).filter(|&v| tree[v].len() >= 2).collect::<Vec<_>>();
+            assert_eq!(ll.articulations, cut);
+        }
+    }
 }
@@ -0,0 +1,259 @@
+use crate::math::algebra::num_trait;
+
+pub mod min_cost_flow {
+    use super::num_trait::*;
+    use std::cmp::*;
+    use std::collections::BinaryHeap;
+
+    #[derive(Debug, Clone, Copy)]
+    struct Edge<T> {
+        to: usize,
+        cap: T,
+        cost: T,
+        rev: usize,
+    }
+
+    /// State of an edge added by `add_edge`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct FlowEdge<T> {
+        pub from: usize,
+        pub to: usize,
+        pub cap: T,
+        pub flow: T,
+        pub cost: T,
+    }
+
+    /// Minimum cost flow by successive shortest paths (primal-dual)
+    ///
+    /// Each augmenting path is found by Dijkstra on reduced costs, so one `flow` call is
+    /// O(F (E log V)). Negative costs are allowed as long as there is no negative cycle;
+    /// the initial potentials are then computed by Bellman-Ford.
+    #[derive(Debug)]
+    pub struct MinCostFlow<T> {
+        graph: Vec<Vec<Edge<T>>>,
+        pos: Vec<(usize, usize)>,
+    }
+
+    impl<T: Ring + Bounded + Ord> MinCostFlow<T> {
+        pub fn new(size: usize) -> Self {
+            Self { graph: vec![vec![]; size], pos: vec![] }
+        }
+
+        /// Adds an edge and returns its id
+        pub fn add_edge(&mut self, from: usize, to: usize, cap: T, cost: T) -> usize {
+            assert!(from != to, "self loops are not supported");
+            let id = self.pos.len();
+            let len_from = self.graph[from].len();
+            let len_to = self.graph[to].len();
+            self.pos.push((from, len_from));
+            self.graph[from].push(Edge { to, cap, cost, rev: len_to });
+            self.graph[to].push(Edge { to: from, cap: T::zero(), cost: -cost, rev: len_from });
+            id
+        }
+
+        pub fn get_edge(&self, id: usize) -> FlowEdge<T> {
+            let (from, i) = self.pos[id];
+            let e = self.graph[from][i];
+            let r = self.graph[e.to][e.rev];
+            FlowEdge { from, to: e.to, cap: e.cap + r.cap, flow: r.cap, cost: e.cost }
+        }
+
+        pub fn edges(&self) -> Vec<FlowEdge<T>> {
+            (0..self.pos.len()).map(|id| self.get_edge(id)).collect()
+        }
+
+        /// Sends at most `limit` units from `s` to `t` and returns `(flow, cost)`
+        pub fn flow(&mut self, s: usize, t: usize, limit: T) -> (T, T) {
+            *self.slope_with_limit(s, t, limit).last().unwrap()
+        }
+
+        /// Sends as much as possible from `s` to `t` and returns `(flow, cost)`
+        pub fn max_flow(&mut self, s: usize, t: usize) -> (T, T) {
+            self.flow(s, t, T::max_value())
+        }
+
+        /// Breakpoints `(flow, cost)` of the piecewise linear minimum cost as a function of
+        /// the flow amount, starting with `(0, 0)` and ending at the maximum flow
+        pub fn slope(&mut self, s: usize, t: usize) -> Vec<(T, T)> {
+            self.slope_with_limit(s, t, T::max_value())
+        }
+
+        pub fn slope_with_limit(&mut self, s: usize, t: usize, limit: T) -> Vec<(T, T)> {
+            assert!(s != t);
+            let n = self.graph.len();
+            let mut potential = self.initial_potential(s);
+            let mut dist = vec![T::max_value(); n];
+            let mut prev = vec![(0, 0); n];
+            let (mut flow, mut cost) = (T::zero(), T::zero());
+            let mut result = vec![(flow, cost)];
+            let mut last_slope = None;
+            while flow < limit {
+                if !self.dijkstra(s, t, &potential, &mut dist, &mut prev) { break }
+                for v in 0..n {
+                    if dist[v] != T::max_value() {
+                        potential[v] += dist[v];
+                    }
+                }
+                let mut d = limit - flow;
+                let mut v = t;
+                while v != s {
+                    let (p, i) = prev[v];
+                    d = min(d, self.graph[p][i].cap);
+                    v = p;
+                }
+                let mut v = t;
+                while v != s {
+                    let (p, i) = prev[v];
+                    let rev = self.graph[p][i].rev;
+                    self.graph[p][i].cap -= d;
+                    self.graph[v][rev].cap += d;
+                    v = p;
+                }
+                let unit = potential[t] - potential[s];
+                flow += d;
+                cost += d * unit;
+                if last_slope == Some(unit) {
+                    result.pop();
+                }
+                result.push((flow, cost));
+                last_slope = Some(unit);
+            }
+            result
+        }
+
+        /// Shortest distances from `s` by Bellman-Ford if some residual edge has a negative cost
+        fn initial_potential(&self, s: usize) -> Vec<T> {
+            let n = self.graph.len();
+            let negative = self.graph.iter().flatten().any(|e| e.cap > T::zero() && e.cost < T::zero());
+            if !negative {
+                return vec![T::zero(); n];
+            }
+            let mut dist = vec![T::max_value(); n];
+            dist[s] = T::zero();
+            for _ in 0..n {
+                let mut updated = false;
+                for v in 0..n {
+                    if dist[v] == T::max_value() { continue }
+                    for e in self.graph[v].iter() {
+                        if e.cap > T::zero() && dist[v] + e.cost < dist[e.to] {
+                            dist[e.to] = dist[v] + e.cost;
+                            updated = true;
+                        }
+                    }
+                }
+                if !updated { break }
+            }
+            dist.into_iter().map(|d| if d == T::max_value() { T::zero() } else { d }).collect()
+        }
+
+        fn dijkstra(
+            &self,
+            s: usize,
+            t: usize,
+            potential: &[T],
+            dist: &mut [T],
+            prev: &mut [(usize, usize)],
+        ) -> bool {
+            for d in dist.iter_mut() {
+                *d = T::max_value();
+            }
+            dist[s] = T::zero();
+            let mut pq = BinaryHeap::new();
+            pq.push(Reverse((T::zero(), s)));
+            while let Some(Reverse((d, v))) = pq.pop() {
+                if dist[v] < d { continue }
+                for (i, e) in self.graph[v].iter().enumerate() {
+                    if e.cap <= T::zero() { continue }
+                    let nd = d + e.cost + potential[v] - potential[e.to];
+                    if nd < dist[e.to] {
+                        dist[e.to] = nd;
+                        prev[e.to] = (v, i);
+                        pq.push(Reverse((nd, e.to)));
+                    }
+                }
+            }
+            dist[t] != T::max_value()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::min_cost_flow::*;
+
+    #[test]
+    fn test_min_cost_flow() {
+        let mut g = MinCostFlow::new(4);
+        let e0 = g.add_edge(0, 1, 2, 1);
+        g.add_edge(0, 2, 1, 2);
+        g.add_edge(1, 2, 1, 1);
+        g.add_edge(1, 3, 1, 3);
+        let e4 = g.add_edge(2, 3, 2, 1);
+        assert_eq!(g.flow(0, 3, 2), (2, 6));
+        assert_eq!(g.get_edge(e0), FlowEdge { from: 0, to: 1, cap: 2, flow: 1, cost: 1 });
+        assert_eq!(g.get_edge(e4).flow, 2);
+        let cost = g.edges().iter().map(|e| e.flow * e.cost).sum::<i64>();
+        assert_eq!(cost, 6);
+        // only 0 -> 1 -> 3 is left
+        assert_eq!(g.flow(0, 3, 10), (1, 4));
+    }
+
+    #[test]
+    fn test_min_cost_flow_slope() {
+        let mut g = MinCostFlow::new(4);
+        g.add_edge(0, 1, 2, 1);
+        g.add_edge(0, 2, 1, 2);
+        g.add_edge(1, 2, 1, 1);
+        g.add_edge(1, 3, 1, 3);
+        g.add_edge(2, 3, 2, 1);
+        assert_eq!(g.slope(0, 3), vec![(0, 0), (2, 6), (3, 10)]);
+    }
+
+    #[test]
+    fn test_min_cost_flow_negative_cost() {
+        let mut g = MinCostFlow::new(4);
+        g.add_edge(0, 1, 1, 3);
+        g.add_edge(0, 2, 1, -2);
+        g.add_edge(1, 3, 1, -5);
+        g.add_edge(2, 3, 1, 4);
+        g.add_edge(2, 1, 1, -1);
+        assert_eq!(g.slope(0, 3), vec![(0, 0), (1, -8), (2, 0)]);
+    }
+
+    #[test]
+    fn test_min_cost_flow_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            // every s-t path in a layered graph has `layers + 1` edges, so shifting all
+            // costs by `shift` shifts the total cost by `shift * (layers + 1) * flow`
+            let (layers, width, shift) = (4, 4, 20i64);
+            let n = layers * width + 2;
+            let (s, t) = (n - 2, n - 1);
+            let mut neg = MinCostFlow::new(n);
+            let mut pos = MinCostFlow::new(n);
+            let mut add = |u: usize, v: usize, cap: i64, cost: i64| {
+                neg.add_edge(u, v, cap, cost);
+                pos.add_edge(u, v, cap, cost + shift);
+            };
+            for i in 0..width {
+                add(s, i, rng.gen_range(1..5), rng.gen_range(-10..10));
+                add((layers - 1) * width + i, t, rng.gen_range(1..5), rng.gen_range(-10..10));
+            }
+            for l in 0..layers - 1 {
+                for _ in 0..2 * width {
+                    let u = l * width + rng.gen_range(0..width);
+                    let v = (l + 1) * width + rng.gen_range(0..width);
+                    add(u, v, rng.gen_range(1..5), rng.gen_range(-10..10));
+                }
+            }
+            let len = layers as i64 + 1;
+            let (f1, c1) = neg.max_flow(s, t);
+            let (f2, c2) = pos.max_flow(s, t);
+            assert_eq!(f1, f2);
+            assert_eq!(c1 + shift * len * f1, c2);
+            let cost = neg.edges().iter().map(|e| e.flow * e.cost).sum::<i64>();
+            assert_eq!(cost, c1);
+        }
+    }
+}
@@ -10,3 +10,18 @@ pub mod topological_sort;
 pub mod ford_fulkerson;
 pub mod dinic;
 pub mod hl_decomposition;
+pub mod csr_graph;
+pub mod bfs;
+pub mod grid;
+pub mod johnson;
+pub mod min_cost_flow;
+pub mod lower_bound_flow;
+pub mod push_relabel;
+pub mod hopcroft_karp;
+pub mod hungarian;
+pub mod blossom;
+pub mod stoer_wagner;
+pub mod gomory_hu;
+pub mod boruvka;
+pub mod directed_mst;
+pub mod two_sat;
@@ -1,86 +1,112 @@
 //! Verified [AOJ GRL 2A](http://judge.u-aizu.ac.jp/onlinejudge/review.jsp?rid=5086976#1)
 use crate::math::algebra::num_trait;
+use crate::graph::base::graph;
 
 pub mod prim {
     use super::num_trait::*;
+    use super::graph::*;
     use std::cmp::*;
     use std::collections::BinaryHeap;
 
+    /// Minimum spanning forest of an undirected graph
     #[derive(Debug, Clone)]
-    pub struct Edge<T: AbelGroup + Eq + Ord> {
-        to: usize,
-        cost: T,
+    pub struct Prim<'a, T, G = Graph<T>> {
+        graph: &'a G,
+        edges: Vec<&'a Edge<T>>,
     }
 
-    impl<T: AbelGroup + Eq + Ord> PartialEq for Edge<T> {
-        fn eq(&self, other: &Self) -> bool {
-            self.cost.eq(&other.cost)
-        }
-    }
-    impl<T: AbelGroup + Eq + Ord> PartialOrd for Edge<T> {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            self.cost.partial_cmp(&other.cost)
-        }
-    }
-    impl<T: AbelGroup + Eq + Ord> Eq for Edge<T> {}
-    impl<T: AbelGroup + Eq + Ord> Ord for Edge<T> {
-        fn cmp(&self, other: &Self) -> Ordering {
-            self.cost.cmp(&other.cost)
-        }
-    }
-
-    pub struct Prim<T: AbelGroup + Eq + Ord> {
-        edges: Vec<Vec<Edge<T>>>,
-    }
-
-    impl<T: AbelGroup + Eq + Ord> Prim<T> {
-        pub fn new(size: usize) -> Self {
-            let edges = vec![vec![]; size];
-            Self { edges }
-        }
-
-        pub fn add_edge_undirected(&mut self, from: usize, to: usize, cost: T) {
-            self.edges[from].push(Edge { to, cost });
-            self.edges[to].push(Edge { to: from, cost });
+    impl<'a, T, G> Prim<'a, T, G>
+    where
+        T: AbelGroup + Ord,
+        G: AdjacencyList<Cost = T>,
+    {
+        pub fn new(graph: &'a G) -> Self {
+            Self { graph, edges: vec![] }
         }
 
+        /// Returns the total weight of the minimum spanning forest.
         pub fn build(&mut self) -> T {
-            let n = self.edges.len();
+            let graph = self.graph;
+            let n = graph.vertex_count();
             let mut visited = vec![false; n];
             let mut heap = BinaryHeap::new();
-            let s = Edge { to: 0, cost: T::zero() };
-            heap.push(Reverse(&s));
             let mut weight_sum = T::zero();
-            while !heap.is_empty() {
-                let Edge { to, cost } = heap.pop().unwrap().0;
-                if visited[*to] { continue }
-                visited[*to] = true;
-                weight_sum += *cost;
-                for edge in self.edges[*to].iter() {
-                    heap.push(Reverse(edge));
+            self.edges.clear();
+            for root in 0..n {
+                if visited[root] { continue }
+                visited[root] = true;
+                for (i, edge) in graph.edges(root).iter().enumerate() {
+                    heap.push(Reverse((edge.cost, root, i)));
+                }
+                while let Some(Reverse((cost, v, i))) = heap.pop() {
+                    let edge = &graph.edges(v)[i];
+                    if visited[edge.to] { continue }
+                    visited[edge.to] = true;
+                    weight_sum += cost;
+                    self.edges.push(edge);
+                    for (i, next) in graph.edges(edge.to).iter().enumerate() {
+                        if !visited[next.to] {
+                            heap.push(Reverse((next.cost, edge.to, i)));
+                        }
+                    }
                 }
             }
             weight_sum
         }
+
+        /// Edges of the minimum spanning forest in the order they were added
+        pub fn edges(&self) -> &[&'a Edge<T>] {
+            &self.edges
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::graph::*;
     use super::prim::*;
+
     #[test]
     fn test_prim() {
-        let mut pr = Prim::new(6);
-        pr.add_edge_undirected(0, 1, 1);
-        pr.add_edge_undirected(0, 2, 3);
-        pr.add_edge_undirected(1, 2, 1);
-        pr.add_edge_undirected(1, 3, 7);
-        pr.add_edge_undirected(2, 4, 1);
-        pr.add_edge_undirected(1, 4, 3);
-        pr.add_edge_undirected(3, 4, 1);
-        pr.add_edge_undirected(3, 5, 1);
-        pr.add_edge_undirected(4, 5, 6);
+        let mut g = Graph::new(6);
+        g.add_edge_undirected(0, 1, 1);
+        g.add_edge_undirected(0, 2, 3);
+        g.add_edge_undirected(1, 2, 1);
+        g.add_edge_undirected(1, 3, 7);
+        g.add_edge_undirected(2, 4, 1);
+        g.add_edge_undirected(1, 4, 3);
+        g.add_edge_undirected(3, 4, 1);
+        g.add_edge_undirected(3, 5, 1);
+        g.add_edge_undirected(4, 5, 6);
+        let mut pr = Prim::new(&g);
         let w = pr.build();
         assert_eq!(w, 5);
+        let mut ids = pr.edges().iter().map(|e| e.id).collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, vec![0, 2, 4, 6, 7]);
+    }
+
+    #[test]
+    fn test_prim_matches_kruskal() {
+        use crate::graph::kruskal::kruskal::Kruskal;
+        use crate::graph::csr_graph::csr_graph::CsrGraph;
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(1..30);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..60) {
+                g.add_edge_undirected(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..100i64));
+            }
+            let mut kr = Kruskal::new(&g);
+            let w = kr.build();
+            let mut pr = Prim::new(&g);
+            assert_eq!(pr.build(), w);
+            assert_eq!(pr.edges().len(), kr.edges().len());
+            assert_eq!(pr.edges().iter().map(|e| e.cost).sum::<i64>(), w);
+            let csr = CsrGraph::from(&g);
+            let mut pr = Prim::new(&csr);
+            assert_eq!(pr.build(), w);
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,116 @@
+use crate::math::algebra::num_trait;
+use crate::graph::base::graph;
+
+pub mod stoer_wagner {
+    use super::num_trait::*;
+    use super::graph::*;
+
+    /// Global minimum cut of an undirected graph with non-negative weights in O(V^3)
+    ///
+    /// Returns the cut value and the side of each vertex (`true` for one of the two parts).
+    /// Requires at least two vertices.
+    pub fn stoer_wagner<T: ComMonoid + Bounded + Ord>(graph: &Graph<T>) -> (T, Vec<bool>) {
+        let n = graph.0.len();
+        assert!(n >= 2);
+        let mut w = vec![vec![T::zero(); n]; n];
+        for edge in graph.edge_list() {
+            if edge.from == edge.to { continue }
+            w[edge.from][edge.to] += edge.cost;
+            w[edge.to][edge.from] += edge.cost;
+        }
+        let mut groups = (0..n).map(|v| vec![v]).collect::<Vec<_>>();
+        let mut active = (0..n).collect::<Vec<_>>();
+        let mut best = T::max_value();
+        let mut best_group = vec![];
+        while active.len() > 1 {
+            let mut weight = vec![T::zero(); n];
+            let mut added = vec![false; n];
+            let mut prev = active[0];
+            for k in 0..active.len() {
+                let mut v = None;
+                for &u in active.iter() {
+                    if !added[u] && v.map_or(true, |v: usize| weight[u] > weight[v]) {
+                        v = Some(u);
+                    }
+                }
+                let v = v.unwrap();
+                added[v] = true;
+                if k + 1 < active.len() {
+                    for &u in active.iter() {
+                        weight[u] += w[v][u];
+                    }
+                    prev = v;
+                    continue;
+                }
+                // `v` is the last vertex: the phase cut separates it from the rest
+                if weight[v] < best {
+                    best = weight[v];
+                    best_group = groups[v].clone();
+                }
+                for &u in active.iter() {
+                    if u == prev || u == v { continue }
+                    let x = w[v][u];
+                    w[prev][u] += x;
+                    w[u][prev] += x;
+                }
+                let moved = std::mem::take(&mut groups[v]);
+                groups[prev].extend(moved);
+                active.retain(|&u| u != v);
+            }
+        }
+        let mut side = vec![false; n];
+        for v in best_group {
+            side[v] = true;
+        }
+        (best, side)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::graph::*;
+    use super::stoer_wagner::*;
+
+    fn cut_value(g: &Graph<i64>, side: &[bool]) -> i64 {
+        g.edge_list().iter().filter(|e| side[e.from] != side[e.to]).map(|e| e.cost).sum()
+    }
+
+    #[test]
+    fn test_stoer_wagner() {
+        // the classic example from the original paper
+        let mut g = Graph::new(8);
+        let edges = [
+            (0, 1, 2), (0, 4, 3), (1, 2, 3), (1, 4, 2), (1, 5, 2), (2, 3, 4), (2, 6, 2),
+            (3, 6, 2), (3, 7, 2), (4, 5, 3), (5, 6, 1), (6, 7, 3),
+        ];
+        for &(u, v, c) in edges.iter() {
+            g.add_edge_undirected(u, v, c);
+        }
+        let (value, side) = stoer_wagner(&g);
+        assert_eq!(value, 4);
+        assert_eq!(cut_value(&g, &side), 4);
+        assert!(side[2] == side[3] && side[3] == side[6] && side[6] == side[7]);
+        assert!(side[0] != side[2]);
+    }
+
+    #[test]
+    fn test_stoer_wagner_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(2..9);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..20) {
+                g.add_edge_undirected(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..10i64));
+            }
+            let (value, side) = stoer_wagner(&g);
+            assert!(side.iter().any(|&b| b) && side.iter().any(|&b| !b));
+            assert_eq!(cut_value(&g, &side), value);
+            let brute = (1..(1u32 << n) - 1).map(|mask| {
+                let side = (0..n).map(|v| mask >> v & 1 == 1).collect::<Vec<_>>();
+                cut_value(&g, &side)
+            }).min().unwrap();
+            assert_eq!(value, brute);
+        }
+    }
+}
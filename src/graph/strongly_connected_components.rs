@@ -1,48 +1,63 @@
 //! Verified [AOJ GRL 3C](http://judge.u-aizu.ac.jp/onlinejudge/review.jsp?rid=5089244#1)
 use crate::graph::base::graph;
-use crate::math::algebra::num_trait;
+use crate::graph::csr_graph::csr_graph;
 
 pub mod strongly_connected_components {
     use super::graph::*;
-    use super::num_trait::*;
+    use super::csr_graph::CsrGraph;
+    use std::marker::PhantomData;
 
+    /// Strongly connected components by Kosaraju's algorithm
+    ///
+    /// Component ids are in topological order: every edge goes to the same or a later component.
     #[derive(Debug)]
-    pub struct StronglyConnectedComponents<'a, T> {
-        graph: &'a Graph<T>,
-        rgraph: Graph<T>,
+    pub struct StronglyConnectedComponents<'a, T, G = Graph<T>> {
+        graph: &'a G,
+        rgraph: CsrGraph<()>,
         visited: Vec<bool>,
         cmp: Vec<isize>,
         ord: Vec<usize>,
         components: Vec<Vec<usize>>,
+        _marker: PhantomData<T>,
     }
 
-    impl<'a, T> StronglyConnectedComponents<'a, T>
-    where
-        T: AbelGroup + Eq + Ord
-    {
-        pub fn new(graph: &'a Graph<T>) -> Self {
-            let n = graph.0.len();
-            let mut rgraph = Graph::<T>::new(n);
-            for v in 0..n {
-                for edge in graph[v].iter() {
-                    rgraph[edge.to].push(Edge { to: v, cost: edge.cost })
-                }
-            }
+    impl<'a, T, G: AdjacencyList<Cost = T>> StronglyConnectedComponents<'a, T, G> {
+        pub fn new(graph: &'a G) -> Self {
+            let n = graph.vertex_count();
+            let redges = (0..n).flat_map(|v| {
+                graph.edges(v).iter().map(move |edge| (edge.to, v, ()))
+            }).collect::<Vec<_>>();
+            let rgraph = CsrGraph::new(n, &redges);
             let visited = vec![false; n];
             let cmp = vec![-1; n];
             let ord = vec![];
             let components = vec![];
-            Self { graph, rgraph, visited, cmp, ord, components }
+            Self { graph, rgraph, visited, cmp, ord, components, _marker: PhantomData }
         }
 
         pub fn same(&self, s: usize, t: usize) -> bool {
             self.cmp[s] == self.cmp[t]
         }
 
+        /// Id of the component containing `v`
+        pub fn component(&self, v: usize) -> usize {
+            self.cmp[v] as usize
+        }
+
+        /// Number of components
+        pub fn count(&self) -> usize {
+            self.components.len()
+        }
+
+        /// Vertices of each component, indexed by component id
+        pub fn components(&self) -> &[Vec<usize>] {
+            &self.components
+        }
+
         fn dfs(&mut self, s: usize) {
             if self.visited[s] { return }
             self.visited[s] = true;
-            for edge in self.graph[s].iter() {
+            for edge in self.graph.edges(s).iter() {
                 self.dfs(edge.to);
             }
             self.ord.push(s);
@@ -55,13 +70,14 @@ pub mod strongly_connected_components {
                 self.components.push(Vec::new());
             }
             self.components[ctr as usize].push(s);
-            for edge in self.rgraph[s].clone().iter() {
-                self.rdfs(edge.to, ctr);
+            for i in 0..self.rgraph.degree(s) {
+                let to = self.rgraph[s][i].to;
+                self.rdfs(to, ctr);
             }
         }
 
         pub fn build(&mut self) {
-            for i in 0..self.graph.0.len() {
+            for i in 0..self.graph.vertex_count() {
                 self.dfs(i);
             }
             self.ord.reverse();
@@ -76,6 +92,22 @@ pub mod strongly_connected_components {
             }
         }
     }
+
+    impl<'a, T: Clone, G: AdjacencyList<Cost = T>> StronglyConnectedComponents<'a, T, G> {
+        /// DAG of the components; edges inside a component are dropped and parallel edges are kept.
+        pub fn condensation(&self) -> Graph<T> {
+            let mut dag = Graph::new(self.count());
+            for v in 0..self.graph.vertex_count() {
+                for edge in self.graph.edges(v).iter() {
+                    let (from, to) = (self.component(v), self.component(edge.to));
+                    if from != to {
+                        dag.add_edge(from, to, edge.cost.clone());
+                    }
+                }
+            }
+            dag
+        }
+    }
 }
 
 #[cfg(test)]
@@ -99,4 +131,79 @@ mod tests {
         assert!(scc.same(2, 3));
         assert!(scc.same(3, 4));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_strongly_connected_components_csr() {
+        use crate::graph::csr_graph::csr_graph::CsrGraph;
+        let edges = [(0, 1, 1), (1, 0, 1), (1, 2, 1), (2, 4, 1), (4, 3, 1), (3, 2, 1)];
+        let g = CsrGraph::new(5, &edges);
+        let mut scc = StronglyConnectedComponents::new(&g);
+        scc.build();
+        assert!(scc.same(0, 1));
+        assert!(!scc.same(0, 3));
+        assert!(scc.same(2, 4));
+    }
+
+    #[test]
+    fn test_condensation() {
+        let mut g = Graph::new(6);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 0, 2);
+        g.add_edge(5, 0, 3);
+        g.add_edge(1, 2, 4);
+        g.add_edge(2, 4, 5);
+        g.add_edge(4, 3, 6);
+        g.add_edge(3, 2, 7);
+        let mut scc = StronglyConnectedComponents::new(&g);
+        scc.build();
+        assert_eq!(scc.count(), 3);
+        let ids = (0..6).map(|v| scc.component(v)).collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 1, 2, 2, 2, 0]);
+        let mut comps = scc.components().to_vec();
+        comps.iter_mut().for_each(|c| c.sort());
+        assert_eq!(comps, vec![vec![5], vec![0, 1], vec![2, 3, 4]]);
+        let dag = scc.condensation();
+        let edges = dag.edge_list().iter().map(|e| (e.from, e.to, e.cost)).collect::<Vec<_>>();
+        assert_eq!(edges, vec![(1, 2, 4), (0, 1, 3)]);
+    }
+
+    #[test]
+    fn test_topological_component_ids() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(1..10);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..20) {
+                g.add_edge(rng.gen_range(0..n), rng.gen_range(0..n), ());
+            }
+            let mut scc = StronglyConnectedComponents::new(&g);
+            scc.build();
+            // reach[u][v]: v is reachable from u
+            let mut reach = vec![vec![false; n]; n];
+            for (u, row) in reach.iter_mut().enumerate() {
+                let mut stack = vec![u];
+                row[u] = true;
+                while let Some(v) = stack.pop() {
+                    for e in g[v].iter() {
+                        if !row[e.to] {
+                            row[e.to] = true;
+                            stack.push(e.to);
+                        }
+                    }
+                }
+            }
+            for (u, row) in reach.iter().enumerate() {
+                for (v, &r) in row.iter().enumerate() {
+                    assert_eq!(scc.same(u, v), r && reach[v][u]);
+                    if r {
+                        assert!(scc.component(u) <= scc.component(v));
+                    }
+                }
+            }
+            for e in scc.condensation().edge_list() {
+                assert!(e.from < e.to);
+            }
+        }
+    }
+}
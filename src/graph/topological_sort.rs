@@ -1,49 +1,148 @@
 //! Verified [AOJ GRL 4A](http://judge.u-aizu.ac.jp/onlinejudge/review.jsp?rid=5089290#1)
 //! Verified [AOJ GRL 4B](http://judge.u-aizu.ac.jp/onlinejudge/review.jsp?rid=5089295#1)
+use crate::math::algebra::num_trait;
 
 pub mod topological_sort {
-    use std::collections::VecDeque;
+    use super::num_trait::*;
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, VecDeque};
 
-    #[derive(Debug)]
-    pub struct TopologicalSortGraph {
-        graph: Vec<Vec<usize>>,
-        degree: Vec<isize>,
+    /// DAG whose edges optionally carry a weight of type `T`
+    #[derive(Debug, Clone)]
+    pub struct TopologicalSortGraph<T = ()> {
+        graph: Vec<Vec<(usize, T)>>,
+        degree: Vec<usize>,
     }
 
     impl TopologicalSortGraph {
         pub fn new(size: usize) -> Self {
-            let graph = vec![vec![]; size];
+            Self::new_weighted(size)
+        }
+
+        pub fn add_edge(&mut self, from: usize, to: usize) {
+            self.add_weighted_edge(from, to, ());
+        }
+    }
+
+    impl<T> TopologicalSortGraph<T> {
+        /// Graph whose edges are added by `add_weighted_edge`
+        pub fn new_weighted(size: usize) -> Self {
+            let graph = (0..size).map(|_| vec![]).collect();
             let degree = vec![0; size];
             Self { graph, degree }
         }
 
-        pub fn add_edge(&mut self, from: usize, to: usize) {
-            self.graph[from].push(to);
+        pub fn add_weighted_edge(&mut self, from: usize, to: usize, cost: T) {
+            self.graph[from].push((to, cost));
             self.degree[to] += 1;
         }
 
-        pub fn sort(&mut self) -> Option<Vec<usize>> {
-            let mut q = VecDeque::new();
+        /// Kahn's algorithm taking the next vertex from `pop`; the order misses the vertices
+        /// on or after a cycle.
+        fn kahn<Q>(&self, mut queue: Q, push: impl Fn(&mut Q, usize), pop: impl Fn(&mut Q) -> Option<usize>) -> Vec<usize> {
+            let mut degree = self.degree.clone();
             let mut ret = vec![];
-            for i in 0..self.graph.len() {
-                if self.degree[i] == 0 {
-                    q.push_back(i);
+            for (i, &d) in degree.iter().enumerate() {
+                if d == 0 {
+                    push(&mut queue, i);
                 }
             }
-            while !q.is_empty() {
-                let v = q.pop_front().unwrap();
+            while let Some(v) = pop(&mut queue) {
                 ret.push(v);
-                for to in self.graph[v].iter() {
-                    self.degree[*to] -= 1;
-                    if self.degree[*to] == 0 {
-                        q.push_back(*to);
+                for &(to, _) in self.graph[v].iter() {
+                    degree[to] -= 1;
+                    if degree[to] == 0 {
+                        push(&mut queue, to);
+                    }
+                }
+            }
+            ret
+        }
+
+        fn complete(&self, order: Vec<usize>) -> Option<Vec<usize>> {
+            if order.len() == self.graph.len() { Some(order) } else { None }
+        }
+
+        /// Returns `None` if there is a cycle.
+        pub fn sort(&self) -> Option<Vec<usize>> {
+            self.complete(self.kahn(VecDeque::new(), |q, v| q.push_back(v), |q| q.pop_front()))
+        }
+
+        /// Lexicographically smallest topological order in O((V + E) log V)
+        pub fn lexicographically_smallest(&self) -> Option<Vec<usize>> {
+            let order = self.kahn(BinaryHeap::new(), |q, v| q.push(Reverse(v)), |q| q.pop().map(|Reverse(v)| v));
+            self.complete(order)
+        }
+
+        /// A directed cycle `v_0 -> v_1 -> ... -> v_k -> v_0`, or `None` if the graph is a DAG
+        pub fn find_cycle(&self) -> Option<Vec<usize>> {
+            let n = self.graph.len();
+            let mut removed = vec![false; n];
+            for v in self.kahn(VecDeque::new(), |q, v| q.push_back(v), |q| q.pop_front()) {
+                removed[v] = true;
+            }
+            // every vertex left by Kahn's algorithm has a predecessor among them
+            let mut pred = vec![usize::MAX; n];
+            for v in (0..n).filter(|&v| !removed[v]) {
+                for &(to, _) in self.graph[v].iter() {
+                    if !removed[to] {
+                        pred[to] = v;
+                    }
+                }
+            }
+            let mut v = (0..n).find(|&v| !removed[v])?;
+            let mut seen = vec![false; n];
+            while !seen[v] {
+                seen[v] = true;
+                v = pred[v];
+            }
+            let mut cycle = vec![v];
+            let mut u = pred[v];
+            while u != v {
+                cycle.push(u);
+                u = pred[u];
+            }
+            cycle.reverse();
+            Some(cycle)
+        }
+
+        /// Number of paths from `s` to each vertex modulo `modulo`, or `None` if there is a cycle
+        pub fn count_paths(&self, s: usize, modulo: u64) -> Option<Vec<u64>> {
+            let mut dp = vec![0; self.graph.len()];
+            dp[s] = 1 % modulo;
+            for v in self.sort()? {
+                for &(to, _) in self.graph[v].iter() {
+                    dp[to] = ((dp[to] as u128 + dp[v] as u128) % modulo as u128) as u64;
+                }
+            }
+            Some(dp)
+        }
+    }
+
+    impl<T: ComMonoid + Ord> TopologicalSortGraph<T> {
+        /// Maximum total weight of a path and its vertices, or `None` if there is a cycle.
+        /// A single vertex is a path of weight zero.
+        pub fn longest_path(&self) -> Option<(T, Vec<usize>)> {
+            let n = self.graph.len();
+            let mut dist = vec![T::zero(); n];
+            let mut prev = vec![usize::MAX; n];
+            for v in self.sort()? {
+                for &(to, cost) in self.graph[v].iter() {
+                    if dist[to] < dist[v] + cost {
+                        dist[to] = dist[v] + cost;
+                        prev[to] = v;
                     }
                 }
             }
-            match *self.degree.iter().max().unwrap() {
-                0 => Some(ret),
-                _  => None
+            let Some(mut v) = (0..n).max_by_key(|&v| dist[v]) else { return Some((T::zero(), vec![])) };
+            let weight = dist[v];
+            let mut path = vec![v];
+            while prev[v] != usize::MAX {
+                v = prev[v];
+                path.push(v);
             }
+            path.reverse();
+            Some((weight, path))
         }
     }
 }
@@ -65,4 +164,125 @@ mod tests {
         g.add_edge(2, 0);
         assert_eq!(g.sort(), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_lexicographically_smallest() {
+        let mut g = TopologicalSortGraph::new(5);
+        g.add_edge(3, 1);
+        g.add_edge(4, 0);
+        g.add_edge(1, 0);
+        g.add_edge(2, 4);
+        assert_eq!(g.sort(), Some(vec![2, 3, 4, 1, 0]));
+        assert_eq!(g.lexicographically_smallest(), Some(vec![2, 3, 1, 4, 0]));
+        assert_eq!(g.find_cycle(), None);
+        g.add_edge(0, 2);
+        assert_eq!(g.lexicographically_smallest(), None);
+        assert_eq!(g.find_cycle(), Some(vec![2, 4, 0]));
+    }
+
+    #[test]
+    fn test_longest_path_and_count() {
+        let mut g = TopologicalSortGraph::new_weighted(5);
+        g.add_weighted_edge(0, 1, 2);
+        g.add_weighted_edge(0, 2, 5);
+        g.add_weighted_edge(1, 2, 4);
+        g.add_weighted_edge(2, 3, -1);
+        g.add_weighted_edge(1, 3, 1);
+        assert_eq!(g.longest_path(), Some((6, vec![0, 1, 2])));
+        assert_eq!(g.count_paths(0, 1_000_000_007), Some(vec![1, 1, 2, 3, 0]));
+        g.add_weighted_edge(3, 0, 0);
+        assert_eq!(g.longest_path(), None);
+        assert_eq!(g.count_paths(0, 1_000_000_007), None);
+
+        // sums of two residues overflow u64 for a modulus above 2^63
+        let m = u64::MAX - 58;
+        let mut g = TopologicalSortGraph::new(70);
+        for v in 0..69 {
+            g.add_edge(v, v + 1);
+            g.add_edge(v, v + 1);
+        }
+        assert_eq!(g.count_paths(0, m).unwrap()[69], ((1u128 << 69) % m as u128) as u64);
+    }
+
+    #[test]
+    fn test_inferred_unweighted() {
+        let g = TopologicalSortGraph::new(3);
+        assert_eq!(g.sort(), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn test_topological_sort_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..200 {
+            let n = rng.gen_range(1..7);
+            let mut g = TopologicalSortGraph::new_weighted(n);
+            let mut edges = vec![];
+            for _ in 0..rng.gen_range(0..10) {
+                let (u, v, w) = (rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(-5..10i64));
+                g.add_weighted_edge(u, v, w);
+                edges.push((u, v, w));
+            }
+            let is_order = |order: &[usize]| {
+                let mut pos = vec![0; n];
+                order.iter().enumerate().for_each(|(i, &v)| pos[v] = i);
+                edges.iter().all(|&(u, v, _)| pos[u] < pos[v])
+            };
+            let mut perm = (0..n).collect::<Vec<_>>();
+            let mut smallest = None;
+            loop {
+                if is_order(&perm) {
+                    smallest = Some(perm.clone());
+                    break;
+                }
+                // next permutation
+                let Some(i) = (1..n).rev().find(|&i| perm[i - 1] < perm[i]) else { break };
+                let j = (i..n).rev().find(|&j| perm[i - 1] < perm[j]).unwrap();
+                perm.swap(i - 1, j);
+                perm[i..].reverse();
+            }
+            assert_eq!(g.lexicographically_smallest(), smallest);
+            assert_eq!(g.sort().is_some(), smallest.is_some());
+            if let Some(order) = g.sort() {
+                assert!(is_order(&order));
+            }
+            match g.find_cycle() {
+                Some(cycle) => {
+                    assert!(smallest.is_none());
+                    for (i, &u) in cycle.iter().enumerate() {
+                        let v = cycle[(i + 1) % cycle.len()];
+                        assert!(edges.iter().any(|&(a, b, _)| (a, b) == (u, v)));
+                    }
+                }
+                None => assert!(smallest.is_some()),
+            }
+            if smallest.is_none() { continue }
+
+            // brute force over paths by DFS
+            fn dfs(v: usize, w: i64, edges: &[(usize, usize, i64)], paths: &mut Vec<(usize, i64)>) {
+                paths.push((v, w));
+                for &(a, b, c) in edges.iter() {
+                    if a == v {
+                        dfs(b, w + c, edges, paths);
+                    }
+                }
+            }
+            let mut best = 0;
+            for s in 0..n {
+                let mut paths = vec![];
+                dfs(s, 0, &edges, &mut paths);
+                best = paths.iter().map(|p| p.1).max().unwrap().max(best);
+                let counts = g.count_paths(s, 7).unwrap();
+                for (t, &c) in counts.iter().enumerate() {
+                    assert_eq!(c, paths.iter().filter(|p| p.0 == t).count() as u64 % 7);
+                }
+            }
+            let (weight, path) = g.longest_path().unwrap();
+            assert_eq!(weight, best);
+            let total = path.windows(2).map(|p| {
+                edges.iter().filter(|e| (e.0, e.1) == (p[0], p[1])).map(|e| e.2).max().unwrap()
+            }).sum::<i64>();
+            assert_eq!(total, weight);
+        }
+    }
+}
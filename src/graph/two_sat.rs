@@ -0,0 +1,98 @@
+use crate::graph::base::graph;
+use crate::graph::strongly_connected_components::strongly_connected_components;
+
+pub mod two_sat {
+    use super::graph::*;
+    use super::strongly_connected_components::StronglyConnectedComponents;
+
+    /// 2-SAT over `n` boolean variables via strongly connected components
+    ///
+    /// The literal "`x_i == f`" is the vertex `2 * i + f` of the implication graph.
+    #[derive(Debug, Clone)]
+    pub struct TwoSat {
+        n: usize,
+        graph: Graph<()>,
+    }
+
+    impl TwoSat {
+        pub fn new(n: usize) -> Self {
+            Self { n, graph: Graph::new(2 * n) }
+        }
+
+        fn literal(i: usize, f: bool) -> usize {
+            2 * i + f as usize
+        }
+
+        /// Adds the clause `(x_i == f) || (x_j == g)`.
+        pub fn add_clause(&mut self, i: usize, f: bool, j: usize, g: bool) {
+            self.graph.add_edge(Self::literal(i, !f), Self::literal(j, g), ());
+            self.graph.add_edge(Self::literal(j, !g), Self::literal(i, f), ());
+        }
+
+        /// Adds `(x_i == f) => (x_j == g)`.
+        pub fn add_implication(&mut self, i: usize, f: bool, j: usize, g: bool) {
+            self.add_clause(i, !f, j, g);
+        }
+
+        /// Adds `(x_i == f) <=> (x_j == g)`.
+        pub fn add_equivalence(&mut self, i: usize, f: bool, j: usize, g: bool) {
+            self.add_implication(i, f, j, g);
+            self.add_implication(j, g, i, f);
+        }
+
+        /// Forces `x_i == f`.
+        pub fn fix(&mut self, i: usize, f: bool) {
+            self.add_clause(i, f, i, f);
+        }
+
+        /// Returns a satisfying assignment, or `None` if there is none.
+        pub fn satisfiable(&self) -> Option<Vec<bool>> {
+            let mut scc = StronglyConnectedComponents::new(&self.graph);
+            scc.build();
+            (0..self.n).map(|i| {
+                let (t, f) = (scc.component(Self::literal(i, true)), scc.component(Self::literal(i, false)));
+                // a literal later in topological order cannot imply its negation
+                if t == f { None } else { Some(t > f) }
+            }).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::two_sat::*;
+
+    #[test]
+    fn test_two_sat() {
+        let mut ts = TwoSat::new(3);
+        ts.add_clause(0, true, 1, true);
+        ts.add_clause(0, false, 1, false);
+        ts.add_implication(1, true, 2, false);
+        ts.fix(2, true);
+        assert_eq!(ts.satisfiable(), Some(vec![true, false, true]));
+        ts.add_equivalence(0, true, 2, false);
+        assert_eq!(ts.satisfiable(), None);
+    }
+
+    #[test]
+    fn test_two_sat_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for _ in 0..200 {
+            let n = rng.gen_range(1..7);
+            let mut ts = TwoSat::new(n);
+            let mut clauses = vec![];
+            for _ in 0..rng.gen_range(0..12) {
+                let c = (rng.gen_range(0..n), rng.gen(), rng.gen_range(0..n), rng.gen());
+                ts.add_clause(c.0, c.1, c.2, c.3);
+                clauses.push(c);
+            }
+            let holds = |x: &[bool]| clauses.iter().all(|&(i, f, j, g)| x[i] == f || x[j] == g);
+            let exists = (0..1 << n).any(|mask: usize| holds(&(0..n).map(|i| mask >> i & 1 == 1).collect::<Vec<_>>()));
+            match ts.satisfiable() {
+                Some(x) => assert!(holds(&x)),
+                None => assert!(!exists),
+            }
+        }
+    }
+}
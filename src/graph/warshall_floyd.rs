@@ -5,10 +5,12 @@ use crate::{min, chmin};
 pub mod warshall_floyd {
     use super::{min, chmin};
     use super::num_trait::*;
+    use std::cmp::*;
     use std::ops::*;
 
+    /// Distance matrix and next-hop matrix (`next[i][j]` is the vertex after `i` on a shortest path to `j`)
     #[derive(Debug)]
-    pub struct WarshallFloyd<T>(Vec<Vec<T>>);
+    pub struct WarshallFloyd<T>(Vec<Vec<T>>, Vec<Vec<usize>>);
 
     impl<T: AbelGroup + Bounded + Signed> WarshallFloyd<T> {
         #[allow(clippy::needless_range_loop)]
@@ -17,7 +19,8 @@ pub mod warshall_floyd {
             for i in 0..n {
                 d[i][i] = T::zero();
             }
-            Self(d)
+            let next = (0..n).map(|_| (0..n).collect()).collect();
+            Self(d, next)
         }
 
         pub fn add_edge(&mut self, from: usize, to: usize, cost: T) {
@@ -29,19 +32,44 @@ pub mod warshall_floyd {
             self[to][from] = cost;
         }
 
+        /// Closes the distance matrix by `closure` over `MinPlus`, recording next hops.
         pub fn build_graph(&mut self) {
             let n = self.0.len();
-            for k in 0..n {
-                for i in 0..n {
-                    for j in 0..n {
-                        if self[i][k] != T::max_value() && self[k][j] != T::max_value() {
-                            chmin!(self[i][j], self[i][k] + self[k][j]);
-                        }
+            let mut next: Vec<Vec<usize>> = (0..n).map(|_| (0..n).collect()).collect();
+            let mut mat = self.0.iter().map(|row| row.iter().map(|&d| MinPlus(d)).collect()).collect::<Vec<Vec<_>>>();
+            closure_with(&mut mat, |i, j, k| next[i][j] = next[i][k]);
+            self.0 = mat.into_iter().map(|row| row.into_iter().map(|d| d.0).collect()).collect();
+            self.1 = next;
+        }
+
+        /// Adds an edge and updates all distances in O(V^2).
+        /// Edges added before must be closed by `build_graph` or added by this method.
+        pub fn add_edge_and_update(&mut self, from: usize, to: usize, cost: T) {
+            let n = self.0.len();
+            for i in 0..n {
+                if self[i][from] == T::max_value() { continue }
+                for j in 0..n {
+                    if self[to][j] == T::max_value() { continue }
+                    if chmin!(self[i][j], self[i][from] + cost + self[to][j]) {
+                        self.1[i][j] = if i == from { to } else { self.1[i][from] };
                     }
                 }
             }
         }
 
+        /// Vertices of a shortest path from `from` to `to`, both ends included.
+        /// Requires `build_graph` and no negative cycle.
+        pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+            if self[from][to] == T::max_value() { return None }
+            let mut path = vec![from];
+            let mut v = from;
+            while v != to {
+                v = self.1[v][to];
+                path.push(v);
+            }
+            Some(path)
+        }
+
         pub fn has_negative_cycle(&self) -> bool {
             let n = self.0.len();
             (0..n).any(|i| self[i][i] < T::zero())
@@ -60,6 +88,91 @@ pub mod warshall_floyd {
             &mut self.0[index]
         }
     }
+
+    /// Semiring for the generic closure
+    ///
+    /// `plus` chooses between alternative paths and `times` concatenates paths;
+    /// `empty()` (no path) must be the identity of `plus` and absorbing for `times`.
+    pub trait PathSemiring: Copy + PartialEq {
+        fn empty() -> Self;
+        fn plus(self, rhs: Self) -> Self;
+        fn times(self, rhs: Self) -> Self;
+    }
+
+    /// Reachability (or, and)
+    impl PathSemiring for bool {
+        fn empty() -> Self { false }
+        fn plus(self, rhs: Self) -> Self { self || rhs }
+        fn times(self, rhs: Self) -> Self { self && rhs }
+    }
+
+    /// Widest path (max, min): the best bottleneck capacity
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MaxMin<T>(pub T);
+
+    impl<T: Copy + Ord + Bounded> PathSemiring for MaxMin<T> {
+        fn empty() -> Self { MaxMin(T::min_value()) }
+        fn plus(self, rhs: Self) -> Self { MaxMin(max(self.0, rhs.0)) }
+        fn times(self, rhs: Self) -> Self { MaxMin(min(self.0, rhs.0)) }
+    }
+
+    /// Shortest path (min, +); `T::max_value()` means no path
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MinPlus<T>(pub T);
+
+    impl<T: Monoid + PartialOrd + Bounded> PathSemiring for MinPlus<T> {
+        fn empty() -> Self { MinPlus(T::max_value()) }
+        fn plus(self, rhs: Self) -> Self { if rhs.0 < self.0 { rhs } else { self } }
+        fn times(self, rhs: Self) -> Self {
+            if self.0 == T::max_value() || rhs.0 == T::max_value() {
+                MinPlus(T::max_value())
+            } else {
+                MinPlus(self.0 + rhs.0)
+            }
+        }
+    }
+
+    /// Number of paths (+, *); only meaningful on DAGs
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SumProd<T>(pub T);
+
+    impl<T: SemiRing> PathSemiring for SumProd<T> {
+        fn empty() -> Self { SumProd(T::zero()) }
+        fn plus(self, rhs: Self) -> Self { SumProd(self.0 + rhs.0) }
+        fn times(self, rhs: Self) -> Self { SumProd(self.0 * rhs.0) }
+    }
+
+    /// Floyd-Warshall over an arbitrary semiring.
+    ///
+    /// `mat[i][j]` starts as the edge weight (`empty()` if there is no edge) and becomes
+    /// the `plus` over all non-empty paths from `i` to `j`. Unless `plus` is idempotent
+    /// (bool, `MaxMin`, `MinPlus`), the diagonal should not contain the identity of `times`;
+    /// add it afterwards if empty paths should count.
+    pub fn closure<S: PathSemiring>(mat: &mut [Vec<S>]) {
+        closure_with(mat, |_, _, _| {});
+    }
+
+    /// `closure` that calls `update(i, j, k)` whenever `mat[i][j]` changes through `k`
+    #[allow(clippy::needless_range_loop)]
+    pub fn closure_with<S, F>(mat: &mut [Vec<S>], mut update: F)
+    where
+        S: PathSemiring,
+        F: FnMut(usize, usize, usize),
+    {
+        let n = mat.len();
+        for k in 0..n {
+            for i in 0..n {
+                let a = mat[i][k];
+                for j in 0..n {
+                    let c = mat[i][j].plus(a.times(mat[k][j]));
+                    if c != mat[i][j] {
+                        mat[i][j] = c;
+                        update(i, j, k);
+                    }
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -95,4 +208,116 @@ mod tests {
         wf.build_graph();
         assert!(wf.has_negative_cycle());
     }
+
+    #[test]
+    fn test_warshall_floyd_path() {
+        let mut wf = WarshallFloyd::new(5);
+        wf.add_edge(0, 1, 1);
+        wf.add_edge(0, 2, 5);
+        wf.add_edge(1, 2, 2);
+        wf.add_edge(1, 3, 4);
+        wf.add_edge(2, 3, 1);
+        wf.add_edge(3, 2, 7);
+        wf.build_graph();
+        assert_eq!(wf.path(0, 3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(wf.path(3, 2), Some(vec![3, 2]));
+        assert_eq!(wf.path(2, 2), Some(vec![2]));
+        assert_eq!(wf.path(3, 0), None);
+        assert_eq!(wf.path(0, 4), None);
+
+        wf.add_edge_and_update(3, 4, 1);
+        wf.add_edge_and_update(4, 0, 1);
+        assert_eq!(wf[2][1], 4);
+        assert_eq!(wf.path(2, 1), Some(vec![2, 3, 4, 0, 1]));
+        wf.add_edge_and_update(0, 3, 1);
+        assert_eq!(wf[0][3], 1);
+        assert_eq!(wf[1][4], 4);
+        assert_eq!(wf.path(4, 3), Some(vec![4, 0, 3]));
+    }
+
+    #[test]
+    fn test_warshall_floyd_incremental_random() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        let n = 12;
+        let mut wf = WarshallFloyd::new(n);
+        wf.build_graph();
+        let mut edges = vec![];
+        for _ in 0..40 {
+            let (u, v, c) = (rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(1..50i64));
+            edges.push((u, v, c));
+            wf.add_edge_and_update(u, v, c);
+            let mut fresh = WarshallFloyd::new(n);
+            for &(u, v, c) in edges.iter() {
+                if u != v && fresh[u][v] > c {
+                    fresh.add_edge(u, v, c);
+                }
+            }
+            fresh.build_graph();
+            for i in 0..n {
+                assert_eq!(wf[i], fresh[i]);
+                for j in 0..n {
+                    if let Some(path) = wf.path(i, j) {
+                        let d = path.windows(2).map(|w| {
+                            edges.iter().filter(|e| e.0 == w[0] && e.1 == w[1]).map(|e| e.2).min().unwrap()
+                        }).sum::<i64>();
+                        assert_eq!(d, wf[i][j]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_incremental_without_build() {
+        let mut wf = WarshallFloyd::new(4);
+        wf.add_edge_and_update(0, 1, 2);
+        wf.add_edge_and_update(1, 2, 3);
+        wf.add_edge_and_update(2, 3, 1);
+        wf.add_edge_and_update(0, 2, 4);
+        assert_eq!(wf[0], vec![0, 2, 4, 5]);
+        assert_eq!(wf.path(0, 3), Some(vec![0, 2, 3]));
+        assert_eq!(wf.path(1, 3), Some(vec![1, 2, 3]));
+        assert_eq!(wf.path(3, 0), None);
+    }
+
+    #[test]
+    fn test_semiring_closure() {
+        let edges = [(0, 1, 5), (1, 2, 3), (0, 2, 2), (2, 3, 4), (1, 3, 1)];
+        let n = 5;
+
+        let mut reach = vec![vec![false; n]; n];
+        let mut widest = vec![vec![MaxMin::empty(); n]; n];
+        let mut count = vec![vec![SumProd::empty(); n]; n];
+        let mut shortest = vec![vec![MinPlus::empty(); n]; n];
+        for &(u, v, c) in edges.iter() {
+            shortest[u][v] = MinPlus(c);
+            reach[u][v] = true;
+            widest[u][v] = MaxMin(c);
+            count[u][v] = SumProd(1u64);
+        }
+        closure(&mut reach);
+        closure(&mut widest);
+        closure(&mut count);
+        closure(&mut shortest);
+
+        assert!(reach[0][3]);
+        assert!(!reach[3][0]);
+        assert!(!reach[0][0]);
+        assert!(!reach[0][4]);
+        assert_eq!(widest[0][3], MaxMin(3));
+        assert_eq!(widest[0][2], MaxMin(3));
+        assert_eq!(widest[2][0], MaxMin(i32::MIN));
+        // 0-1-3, 0-1-2-3, 0-2-3
+        assert_eq!(count[0][3], SumProd(3));
+        assert_eq!(count[1][3], SumProd(2));
+        assert_eq!(shortest[0][3], MinPlus(6));
+        assert_eq!(shortest[3][0], MinPlus(i32::MAX));
+
+        let mut cyc = vec![vec![false; 3]; 3];
+        cyc[0][1] = true;
+        cyc[1][0] = true;
+        closure(&mut cyc);
+        assert!(cyc[0][0] && cyc[1][1] && !cyc[2][2]);
+    }
 }
\ No newline at end of file
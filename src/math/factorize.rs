@@ -17,23 +17,19 @@ pub fn factorize(n: u64) -> Vec<u64> {
     ret
 }
 
-pub fn factorize_pair(n: u64) -> std::collections::HashMap<u64, u64> {
-    let mut ret = std::collections::HashMap::new();
-    let mut n = n;
-    while n % 2 == 0 {
-        *ret.entry(2).or_insert(0) += 1;
-        n /= 2;
-    }
-    let mut i = 3;
-    while i * i <= n {
-        while n % i == 0 {
-            *ret.entry(i).or_insert(0) += 1;
-            n /= i;
+/// Prime factorization as `(prime, exponent)` pairs in ascending order of primes.
+///
+/// Collects into any map (or `Vec`) implementing `FromIterator`,
+/// e.g. `HashMap<u64, u64>`, `BTreeMap<u64, u64>` or `FastHashMap<u64, u64>`.
+pub fn factorize_pair<M: std::iter::FromIterator<(u64, u64)>>(n: u64) -> M {
+    let mut ret: Vec<(u64, u64)> = vec![];
+    for p in factorize(n) {
+        match ret.last_mut() {
+            Some((q, c)) if *q == p => *c += 1,
+            _ => ret.push((p, 1)),
         }
-        i += 2;
     }
-    if n > 2 { *ret.entry(n).or_insert(0) += 1;}
-    ret
+    ret.into_iter().collect()
 }
 
 #[cfg(test)]
@@ -59,6 +55,20 @@ mod tests {
         map.insert(5, 1);
         map.insert(23, 1);
         map.insert(271, 1);
-        assert_eq!(factorize_pair(498640), map);
+        assert_eq!(factorize_pair::<HashMap<_, _>>(498640), map);
+    }
+
+    #[test]
+    fn test_factorize_pair_generic() {
+        use std::collections::BTreeMap;
+        use crate::utils::hasher::hasher::FastHashMap;
+        let v: Vec<(u64, u64)> = factorize_pair(498640);
+        assert_eq!(v, vec![(2, 4), (5, 1), (23, 1), (271, 1)]);
+        let map: BTreeMap<u64, u64> = factorize_pair(498640);
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), v);
+        let map: FastHashMap<u64, u64> = factorize_pair(1 << 20);
+        assert_eq!(map[&2], 20);
+        assert_eq!(map.len(), 1);
+        assert!(factorize_pair::<Vec<_>>(1).is_empty());
     }
 }
\ No newline at end of file
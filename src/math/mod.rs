@@ -10,3 +10,4 @@ pub mod ntt;
 pub mod fps;
 pub mod linear_algebra;
 pub mod angle;
+pub mod ordered_float;
@@ -0,0 +1,162 @@
+use crate::math::algebra::num_trait;
+
+pub mod ordered_float {
+    use super::num_trait::*;
+    use std::cmp::Ordering;
+    use std::ops::*;
+
+    /// Totally ordered floating point number
+    ///
+    /// NaN is treated as equal to itself and greater than any other value.
+    /// `Bounded::max_value()` is positive infinity, so it can be used as the
+    /// cost type of `Dijkstra`, `BellmanFord` and `WarshallFloyd`.
+    #[derive(Debug, Default, Copy, Clone)]
+    pub struct OrderedFloat<F>(pub F);
+
+    macro_rules! impl_binop {
+        ($t: ty, $($tr: ident $f: ident $tra: ident $fa: ident),*) => {$(
+            impl $tr for OrderedFloat<$t> {
+                type Output = Self;
+                fn $f(self, rhs: Self) -> Self { OrderedFloat(self.0.$f(rhs.0)) }
+            }
+            impl $tra for OrderedFloat<$t> {
+                fn $fa(&mut self, rhs: Self) { self.0.$fa(rhs.0) }
+            }
+        )*};
+    }
+
+    macro_rules! impl_ordered_float {
+        ($($t: ident)*) => {$(
+            impl OrderedFloat<$t> {
+                pub fn value(self) -> $t { self.0 }
+
+                /// `|self - rhs| <= eps`
+                pub fn approx_eq(self, rhs: Self, eps: $t) -> bool {
+                    (self.0 - rhs.0).abs() <= eps
+                }
+            }
+
+            impl PartialEq for OrderedFloat<$t> {
+                fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
+            }
+            impl Eq for OrderedFloat<$t> {}
+            impl PartialOrd for OrderedFloat<$t> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+            }
+            impl Ord for OrderedFloat<$t> {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    match self.0.partial_cmp(&other.0) {
+                        Some(ord) => ord,
+                        None => self.0.is_nan().cmp(&other.0.is_nan()),
+                    }
+                }
+            }
+
+            impl_binop!($t, Add add AddAssign add_assign, Sub sub SubAssign sub_assign,
+                        Mul mul MulAssign mul_assign, Div div DivAssign div_assign,
+                        Rem rem RemAssign rem_assign);
+
+            impl Neg for OrderedFloat<$t> {
+                type Output = Self;
+                fn neg(self) -> Self { OrderedFloat(-self.0) }
+            }
+
+            impl Zero for OrderedFloat<$t> {
+                fn zero() -> Self { OrderedFloat(0.0) }
+                fn is_zero(&self) -> bool { self.0.is_zero() }
+            }
+            impl One for OrderedFloat<$t> {
+                fn one() -> Self { OrderedFloat(1.0) }
+                fn is_one(&self) -> bool { self.0.is_one() }
+            }
+            impl Signed for OrderedFloat<$t> {
+                fn abs(&self) -> Self { OrderedFloat(Signed::abs(&self.0)) }
+                fn is_positive(&self) -> bool { Signed::is_positive(&self.0) }
+                fn is_negative(&self) -> bool { Signed::is_negative(&self.0) }
+            }
+            impl Bounded for OrderedFloat<$t> {
+                fn min_value() -> Self { OrderedFloat($t::NEG_INFINITY) }
+                fn max_value() -> Self { OrderedFloat($t::INFINITY) }
+            }
+            impl Associative for OrderedFloat<$t> {}
+
+            impl From<$t> for OrderedFloat<$t> {
+                fn from(v: $t) -> Self { OrderedFloat(v) }
+            }
+
+            impl std::fmt::Display for OrderedFloat<$t> {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    self.0.fmt(f)
+                }
+            }
+        )*};
+    }
+
+    impl_ordered_float!(f32 f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ordered_float::*;
+    use crate::graph::base::graph::Graph;
+    use crate::graph::dijkstra::dijkstra::Dijkstra;
+    use crate::graph::bellmanford::bellmanford::BellmanFord;
+    use crate::graph::warshall_floyd::warshall_floyd::WarshallFloyd;
+
+    type F = OrderedFloat<f64>;
+    const EPS: f64 = 1e-9;
+
+    #[test]
+    fn test_ordered_float_ord() {
+        let mut v = [F::from(1.5), F::from(f64::NAN), F::from(-2.0), F::from(0.1 + 0.2)];
+        v.sort();
+        assert_eq!(v[0].value(), -2.0);
+        assert!(v[1].approx_eq(F::from(0.3), EPS));
+        assert_eq!(v[2].value(), 1.5);
+        assert!(v[3].value().is_nan());
+        assert_eq!(F::from(f64::NAN), F::from(f64::NAN));
+        assert!(F::from(f64::INFINITY) < F::from(f64::NAN));
+    }
+
+    fn dist(p: (f64, f64), q: (f64, f64)) -> F {
+        F::from((p.0 - q.0).hypot(p.1 - q.1))
+    }
+
+    #[test]
+    fn test_float_shortest_paths() {
+        // Points on a plane, edges weighted by Euclidean distance
+        let points = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.3, 0.7), (2.0, 2.0)];
+        let edges = [(0, 1), (1, 2), (0, 3), (3, 2), (2, 4), (0, 4)];
+        let n = points.len();
+        let mut g = Graph::new(n);
+        let mut wf = WarshallFloyd::new(n);
+        for &(u, v) in edges.iter() {
+            g.add_edge_undirected(u, v, dist(points[u], points[v]));
+            wf.add_edge_undirected(u, v, dist(points[u], points[v]));
+        }
+        let d02 = dist(points[0], points[3]) + dist(points[3], points[2]);
+        let expected = [F::from(0.0), F::from(1.0), d02, dist(points[0], points[3]), dist(points[0], points[4])];
+
+        let mut dijkstra = Dijkstra::new(&g);
+        dijkstra.build_graph(0);
+        let mut bf = BellmanFord::new(&g);
+        assert!(bf.build_graph(0));
+        wf.build_graph();
+        for v in 0..n {
+            assert!(dijkstra.dists[v].approx_eq(expected[v], EPS));
+            assert!(bf.dists[v].approx_eq(expected[v], EPS));
+            assert!(wf[0][v].approx_eq(expected[v], EPS));
+        }
+        assert_eq!(dijkstra.path_to(2), Some(vec![0, 3, 2]));
+    }
+
+    #[test]
+    fn test_float_unreachable() {
+        let mut g = Graph::new(3);
+        g.add_edge(0, 1, F::from(0.5));
+        let mut dijkstra = Dijkstra::new(&g);
+        dijkstra.build_graph(0);
+        assert_eq!(dijkstra.dists[2], F::from(f64::INFINITY));
+        assert_eq!(dijkstra.path_to(2), None);
+    }
+}
@@ -29,10 +29,72 @@ pub mod bitset {
             }
         }
 
+        pub fn len(&self) -> usize {
+            self.size
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.size == 0
+        }
+
+        /// Raw 64-bit words, least significant bit first.
+        pub fn words(&self) -> &[u64] {
+            &self.buf
+        }
+
         pub fn count_ones(&self) -> u32 {
             self.buf.iter().fold(0, |acc, x| acc + x.count_ones())
         }
 
+        /// Number of set bits in the **half-open interval** [l..r).
+        pub fn count_ones_range(&self, l: usize, r: usize) -> u32 {
+            assert!(l <= r && r <= self.size);
+            if l == r { return 0 }
+            let (lq, lr) = (l >> 6, l & 63);
+            let (rq, rr) = (r >> 6, r & 63);
+            let low = !0u64 << lr;
+            if lq == rq {
+                return (self.buf[lq] & low & ((1 << rr) - 1)).count_ones();
+            }
+            let mut ret = (self.buf[lq] & low).count_ones();
+            for x in &self.buf[lq + 1..rq] {
+                ret += x.count_ones();
+            }
+            if rr != 0 {
+                ret += (self.buf[rq] & ((1 << rr) - 1)).count_ones();
+            }
+            ret
+        }
+
+        /// Returns the smallest index of a set bit.
+        pub fn find_first(&self) -> Option<usize> {
+            self.find_from(0)
+        }
+
+        /// Returns the smallest index of a set bit **strictly greater** than `i`.
+        pub fn find_next(&self, i: usize) -> Option<usize> {
+            self.find_from(i + 1)
+        }
+
+        fn find_from(&self, i: usize) -> Option<usize> {
+            if i >= self.size { return None }
+            let mut q = i >> 6;
+            let mut x = self.buf[q] & (!0u64 << (i & 63));
+            loop {
+                if x != 0 {
+                    return Some((q << 6) | x.trailing_zeros() as usize);
+                }
+                q += 1;
+                if q >= self.buf.len() { return None }
+                x = self.buf[q];
+            }
+        }
+
+        pub fn iter_ones(&self) -> BitSetOnes<'_> {
+            let cur = self.buf.first().copied().unwrap_or(0);
+            BitSetOnes { buf: &self.buf, idx: 0, cur }
+        }
+
         fn chomp(&mut self) {
             let r = self.size & 63;
             if r != 0 {
@@ -44,6 +106,26 @@ pub mod bitset {
         }
     }
 
+    pub struct BitSetOnes<'a> {
+        buf: &'a [u64],
+        idx: usize,
+        cur: u64,
+    }
+
+    impl<'a> Iterator for BitSetOnes<'a> {
+        type Item = usize;
+        fn next(&mut self) -> Option<usize> {
+            while self.cur == 0 {
+                self.idx += 1;
+                if self.idx >= self.buf.len() { return None }
+                self.cur = self.buf[self.idx];
+            }
+            let r = self.cur.trailing_zeros() as usize;
+            self.cur &= self.cur - 1;
+            Some((self.idx << 6) | r)
+        }
+    }
+
     impl Index<usize> for BitSet {
         type Output = bool;
         fn index(&self, index: usize) -> &bool {
@@ -300,6 +382,35 @@ mod tests {
         assert_eq!((set1 ^ &set2).count_ones(), 2);
     }
 
+    #[test]
+    #[allow(clippy::needless_range_loop)]
+    fn test_bitset_find_and_count() {
+        use rand::{Rng, thread_rng};
+        let mut rng = thread_rng();
+        for &size in &[0, 1, 63, 64, 65, 200, 1000] {
+            let mut set = BitSet::new(size);
+            let mut v = vec![false; size];
+            for i in 0..size {
+                let b = rng.gen_bool(0.2);
+                set.set(i, b);
+                v[i] = b;
+            }
+            let ones = (0..size).filter(|&i| v[i]).collect::<Vec<_>>();
+            assert_eq!(set.iter_ones().collect::<Vec<_>>(), ones);
+            assert_eq!(set.find_first(), ones.first().copied());
+            for i in 0..size {
+                let next = ones.iter().copied().find(|&j| j > i);
+                assert_eq!(set.find_next(i), next);
+            }
+            for l in (0..=size).step_by(7) {
+                for r in (l..=size).step_by(5) {
+                    let c = (l..r).filter(|&i| v[i]).count() as u32;
+                    assert_eq!(set.count_ones_range(l, r), c);
+                }
+            }
+        }
+    }
+
     // #[cfg(test)]
     // use test::Bencher;
 
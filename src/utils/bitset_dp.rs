@@ -0,0 +1,196 @@
+use crate::utils::bitset::bitset;
+
+pub mod bitset_dp {
+    use super::bitset::BitSet;
+    use std::collections::VecDeque;
+
+    /// Returns the set of sums in [0..=limit] that can be made by
+    /// choosing a subset of `values`.
+    pub fn subset_sum(values: &[usize], limit: usize) -> BitSet {
+        let mut dp = BitSet::new(limit + 1);
+        dp.set(0, true);
+        for &v in values {
+            dp |= &(dp.clone() << v);
+        }
+        dp
+    }
+
+    /// Same as `subset_sum`, but `items[i] = (value, count)` can be
+    /// chosen up to `count` times.
+    ///
+    /// Each item is split into `1, 2, 4, ..., rest` copies, so this runs in
+    /// O(limit / 64 * Σ log(count)).
+    pub fn subset_sum_with_multiplicity(items: &[(usize, usize)], limit: usize) -> BitSet {
+        let mut dp = BitSet::new(limit + 1);
+        dp.set(0, true);
+        for &(v, mut c) in items {
+            let mut k = 1;
+            while c > 0 {
+                let m = std::cmp::min(k, c);
+                dp |= &(dp.clone() << v.saturating_mul(m));
+                c -= m;
+                k <<= 1;
+            }
+        }
+        dp
+    }
+
+    /// `reach[v][u]` is true iff `u` is reachable from `v` (including `v` itself).
+    ///
+    /// Returns `None` if `graph` has a cycle.
+    pub fn transitive_closure(graph: &[Vec<usize>]) -> Option<Vec<BitSet>> {
+        let n = graph.len();
+        let mut degree = vec![0; n];
+        for edges in graph.iter() {
+            for &to in edges.iter() {
+                degree[to] += 1;
+            }
+        }
+        let mut q = (0..n).filter(|&v| degree[v] == 0).collect::<VecDeque<_>>();
+        let mut order = vec![];
+        while let Some(v) = q.pop_front() {
+            order.push(v);
+            for &to in graph[v].iter() {
+                degree[to] -= 1;
+                if degree[to] == 0 {
+                    q.push_back(to);
+                }
+            }
+        }
+        if order.len() != n { return None }
+        let mut reach = vec![BitSet::new(n); n];
+        for &v in order.iter().rev() {
+            let mut r = BitSet::new(n);
+            r.set(v, true);
+            for &to in graph[v].iter() {
+                r |= &reach[to];
+            }
+            reach[v] = r;
+        }
+        Some(reach)
+    }
+
+    /// Boolean matrix product of an `n x m` matrix `a` and an `m x l` matrix `b`,
+    /// each given as a list of rows.
+    ///
+    /// Uses the method of Four Russians with 8-row tables:
+    /// O(n * m * l / (64 * 8) + m * l * 256 / (64 * 8)).
+    pub fn bool_matmul(a: &[BitSet], b: &[BitSet]) -> Vec<BitSet> {
+        let m = b.len();
+        let l = b.first().map_or(0, |row| row.len());
+        assert!(a.iter().all(|row| row.len() == m));
+        let mut c = vec![BitSet::new(l); a.len()];
+        let mut table = vec![BitSet::new(l); 256];
+        for k in (0..m).step_by(8) {
+            let w = std::cmp::min(8, m - k);
+            for mask in 1..1usize << w {
+                let low = mask.trailing_zeros() as usize;
+                table[mask] = table[mask & (mask - 1)].clone() | &b[k + low];
+            }
+            for (row, ci) in a.iter().zip(c.iter_mut()) {
+                let mask = (row.words()[k >> 6] >> (k & 63)) as usize & ((1 << w) - 1);
+                if mask != 0 {
+                    *ci |= &table[mask];
+                }
+            }
+        }
+        c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bitset_dp::*;
+    use super::bitset::BitSet;
+    use rand::{Rng, thread_rng};
+
+    #[test]
+    fn test_subset_sum() {
+        let values = vec![3, 5, 11, 2];
+        let dp = subset_sum(&values, 30);
+        let mut expected = vec![false; 31];
+        for mask in 0..1 << values.len() {
+            let s = (0..values.len()).filter(|i| mask >> i & 1 == 1).map(|i| values[i]).sum::<usize>();
+            expected[s] = true;
+        }
+        assert_eq!((0..31).map(|i| dp[i]).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_subset_sum_with_multiplicity() {
+        let mut rng = thread_rng();
+        for _ in 0..20 {
+            let items = (0..5).map(|_| (rng.gen_range(1..20), rng.gen_range(0..10))).collect::<Vec<(usize, usize)>>();
+            let limit = 300;
+            let dp = subset_sum_with_multiplicity(&items, limit);
+            let mut naive = vec![false; limit + 1];
+            naive[0] = true;
+            for &(v, c) in items.iter() {
+                for _ in 0..c {
+                    for s in (v..=limit).rev() {
+                        naive[s] |= naive[s - v];
+                    }
+                }
+            }
+            assert_eq!((0..=limit).map(|i| dp[i]).collect::<Vec<_>>(), naive);
+        }
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            let n = 70;
+            let mut graph = vec![vec![]; n];
+            for (u, edges) in graph.iter_mut().enumerate() {
+                for v in u + 1..n {
+                    if rng.gen_bool(0.05) {
+                        edges.push(v);
+                    }
+                }
+            }
+            let reach = transitive_closure(&graph).unwrap();
+            for s in 0..n {
+                let mut visited = vec![false; n];
+                let mut stack = vec![s];
+                visited[s] = true;
+                while let Some(v) = stack.pop() {
+                    for &to in graph[v].iter() {
+                        if !visited[to] {
+                            visited[to] = true;
+                            stack.push(to);
+                        }
+                    }
+                }
+                assert_eq!((0..n).map(|t| reach[s][t]).collect::<Vec<_>>(), visited);
+            }
+        }
+        let graph = vec![vec![1], vec![2], vec![0]];
+        assert!(transitive_closure(&graph).is_none());
+    }
+
+    #[test]
+    fn test_bool_matmul() {
+        let mut rng = thread_rng();
+        for &(n, m, l) in &[(1, 1, 1), (5, 7, 9), (30, 130, 70), (64, 64, 64)] {
+            let x = (0..n).map(|_| (0..m).map(|_| rng.gen_bool(0.1)).collect::<Vec<_>>()).collect::<Vec<_>>();
+            let y = (0..m).map(|_| (0..l).map(|_| rng.gen_bool(0.1)).collect::<Vec<_>>()).collect::<Vec<_>>();
+            let to_bitset = |v: &Vec<bool>| {
+                let mut b = BitSet::new(v.len());
+                for (i, &f) in v.iter().enumerate() {
+                    b.set(i, f);
+                }
+                b
+            };
+            let a = x.iter().map(to_bitset).collect::<Vec<_>>();
+            let b = y.iter().map(to_bitset).collect::<Vec<_>>();
+            let c = bool_matmul(&a, &b);
+            for i in 0..n {
+                for j in 0..l {
+                    let e = (0..m).any(|k| x[i][k] && y[k][j]);
+                    assert_eq!(c[i][j], e);
+                }
+            }
+        }
+    }
+}
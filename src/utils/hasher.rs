@@ -0,0 +1,119 @@
+pub mod hasher {
+    use std::collections::{HashMap, HashSet};
+    use std::hash::{BuildHasher, Hasher};
+
+    pub fn splitmix64(mut x: u64) -> u64 {
+        x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        x ^ (x >> 31)
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct SplitMixHasher {
+        hash: u64,
+    }
+
+    impl Hasher for SplitMixHasher {
+        fn write(&mut self, bytes: &[u8]) {
+            for chunk in bytes.chunks(8) {
+                let mut buf = [0u8; 8];
+                buf[..chunk.len()].copy_from_slice(chunk);
+                self.write_u64(u64::from_le_bytes(buf));
+            }
+        }
+
+        fn write_u8(&mut self, i: u8) { self.write_u64(i as u64) }
+        fn write_u16(&mut self, i: u16) { self.write_u64(i as u64) }
+        fn write_u32(&mut self, i: u32) { self.write_u64(i as u64) }
+        fn write_usize(&mut self, i: usize) { self.write_u64(i as u64) }
+        fn write_i8(&mut self, i: i8) { self.write_u64(i as u64) }
+        fn write_i16(&mut self, i: i16) { self.write_u64(i as u64) }
+        fn write_i32(&mut self, i: i32) { self.write_u64(i as u64) }
+        fn write_i64(&mut self, i: i64) { self.write_u64(i as u64) }
+        fn write_isize(&mut self, i: isize) { self.write_u64(i as u64) }
+
+        fn write_u64(&mut self, i: u64) {
+            self.hash = splitmix64(self.hash ^ i);
+        }
+
+        fn finish(&self) -> u64 {
+            self.hash
+        }
+    }
+
+    /// `BuildHasher` with a seed chosen at random on construction,
+    /// so that inputs prepared against a fixed hash function cannot
+    /// degrade the table.
+    #[derive(Debug, Clone, Copy)]
+    pub struct RandomState {
+        seed: u64,
+    }
+
+    impl RandomState {
+        pub fn new() -> Self {
+            Self { seed: rand::random() }
+        }
+
+        pub fn with_seed(seed: u64) -> Self {
+            Self { seed }
+        }
+    }
+
+    impl Default for RandomState {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl BuildHasher for RandomState {
+        type Hasher = SplitMixHasher;
+        fn build_hasher(&self) -> SplitMixHasher {
+            SplitMixHasher { hash: self.seed }
+        }
+    }
+
+    pub type FastHashMap<K, V> = HashMap<K, V, RandomState>;
+    pub type FastHashSet<K> = HashSet<K, RandomState>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hasher::*;
+    use std::hash::{BuildHasher, Hash, Hasher};
+
+    fn hash_u64(s: &RandomState, x: u64) -> u64 {
+        let mut h = s.build_hasher();
+        h.write_u64(x);
+        h.finish()
+    }
+
+    fn hash_str(s: &RandomState, x: &str) -> u64 {
+        let mut h = s.build_hasher();
+        x.hash(&mut h);
+        h.finish()
+    }
+
+    #[test]
+    fn test_hasher_seed() {
+        let s = RandomState::with_seed(42);
+        assert_eq!(hash_u64(&s, 1), hash_u64(&s, 1));
+        assert_ne!(hash_u64(&s, 1), hash_u64(&s, 2));
+        let t = RandomState::with_seed(43);
+        assert_ne!(hash_u64(&s, 1), hash_u64(&t, 1));
+        assert_ne!(hash_str(&s, "abc"), hash_str(&s, "abd"));
+    }
+
+    #[test]
+    fn test_fast_hash_map() {
+        let mut map = FastHashMap::default();
+        for i in 0..1000u64 {
+            *map.entry(i % 37).or_insert(0) += 1;
+        }
+        assert_eq!(map.len(), 37);
+        assert_eq!(map[&0], 28);
+        assert_eq!(map[&36], 27);
+        let set = (0..100).map(|i| i * i % 10).collect::<FastHashSet<i32>>();
+        assert_eq!(set.len(), 6);
+    }
+}
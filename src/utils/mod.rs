@@ -3,3 +3,5 @@ pub mod itertools;
 pub mod permutations;
 pub mod binary_search;
 pub mod bitset;
+pub mod bitset_dp;
+pub mod hasher;